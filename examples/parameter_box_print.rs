@@ -1,4 +1,4 @@
-use parameter_box::{unwrap_result, ParameterBox, ParameterBoxError};
+use parameter_box::{unwrap_result, ParameterBox};
 
 fn main() {
     let mut param_box = ParameterBox::new();
@@ -8,7 +8,7 @@ fn main() {
     unwrap_result!(param_box.add::<f64>("param_f64"));
     unwrap_result!(param_box.add::<String>("param_String"));
 
-    unwrap_result!(param_box.set_unvisible("param_i64"));
+    unwrap_result!(param_box.set_invisible("param_i64"));
 
     unwrap_result!(param_box.set_value::<u8>("param_u8", 3));
     unwrap_result!(param_box.set_value::<i64>("param_i64", 98));
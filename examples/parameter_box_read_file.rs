@@ -1,4 +1,4 @@
-use parameter_box::{ParameterBox, ParameterBoxError};
+use parameter_box::ParameterBox;
 
 fn main() {
     let args: Vec<String> = std::env::args().collect();
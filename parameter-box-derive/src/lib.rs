@@ -0,0 +1,77 @@
+// Copyright (c) 2021 Yuichi Ishida
+//
+// Released under the MIT license.
+// see https://opensource.org/licenses/mit-license.php
+
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::{parse_macro_input, Data, DeriveInput, Fields};
+
+/// Derives `From<Struct> for ParameterBox` and `TryFrom<ParameterBox> for Struct`.
+///
+/// Each named field is registered as a parameter whose name is the field name. `try_from` returns
+/// `NotSet` for a parameter without a value and `TypeMismatch` for a parameter of another type.
+#[proc_macro_derive(ParameterBoxFrom)]
+pub fn derive_parameter_box_from(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    let struct_name = &input.ident;
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => {
+                return syn::Error::new_spanned(
+                    struct_name,
+                    "ParameterBoxFrom can only be derived for structs with named fields.",
+                )
+                .to_compile_error()
+                .into()
+            }
+        },
+        _ => {
+            return syn::Error::new_spanned(
+                struct_name,
+                "ParameterBoxFrom can only be derived for structs.",
+            )
+            .to_compile_error()
+            .into()
+        }
+    };
+    let field_idents: Vec<_> = fields.iter().map(|field| &field.ident).collect();
+    let field_names: Vec<_> = field_idents
+        .iter()
+        .map(|ident| ident.as_ref().unwrap().to_string())
+        .collect();
+    let field_types: Vec<_> = fields.iter().map(|field| &field.ty).collect();
+
+    let expanded = quote! {
+        impl ::core::convert::From<#struct_name> for ::parameter_box::ParameterBox {
+            fn from(parameters: #struct_name) -> Self {
+                let mut parameter_box = ::parameter_box::ParameterBox::new();
+                #(
+                    parameter_box
+                        .add::<#field_types>(#field_names)
+                        .expect("Parameter registration failed.");
+                    parameter_box
+                        .set_value::<#field_types>(#field_names, parameters.#field_idents)
+                        .expect("Parameter value setting failed.");
+                )*
+                parameter_box
+            }
+        }
+
+        impl ::core::convert::TryFrom<::parameter_box::ParameterBox> for #struct_name {
+            type Error = ::parameter_box::ParameterBoxError;
+
+            fn try_from(
+                parameter_box: ::parameter_box::ParameterBox,
+            ) -> ::core::result::Result<Self, Self::Error> {
+                ::core::result::Result::Ok(Self {
+                    #(
+                        #field_idents: parameter_box.try_value::<#field_types>(#field_names)?,
+                    )*
+                })
+            }
+        }
+    };
+    expanded.into()
+}
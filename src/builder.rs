@@ -0,0 +1,197 @@
+// Copyright (c) 2021 Yuichi Ishida
+//
+// Released under the MIT license.
+// see https://opensource.org/licenses/mit-license.php
+
+use core::fmt::Display;
+use core::marker::PhantomData;
+
+use crate::{ParameterBox, ParameterBoxError};
+
+/// Builder of a `ParameterBox` configured in one expression.
+///
+/// ```
+/// use parameter_box::ParameterBoxBuilder;
+///
+/// let parameter_box = ParameterBoxBuilder::new()
+///     .add::<f64>("lr")
+///     .value(0.001)
+///     .range_open_open(0.0, 1.0)
+///     .explanation("learning rate")
+///     .done()
+///     .add::<u32>("epochs")
+///     .value(100)
+///     .done()
+///     .build()
+///     .unwrap();
+/// ```
+#[derive(Debug, Default)]
+pub struct ParameterBoxBuilder {
+    parameter_box: ParameterBox,
+    errors: Vec<ParameterBoxError>,
+}
+
+/// Builder of one parameter, which is returned by `ParameterBoxBuilder::add`.
+#[derive(Debug)]
+pub struct ParameterEntryBuilder<T> {
+    builder: ParameterBoxBuilder,
+    name: String,
+    phantom: PhantomData<T>,
+}
+
+impl ParameterBoxBuilder {
+    /// Creates a builder of an empty parameter box.
+    pub fn new() -> Self {
+        Self {
+            parameter_box: ParameterBox::new(),
+            errors: Vec::new(),
+        }
+    }
+
+    /// Adds a parameter and returns its builder; errors are reported by `build`.
+    #[allow(clippy::should_implement_trait)]
+    #[track_caller]
+    pub fn add<T>(mut self, name: &str) -> ParameterEntryBuilder<T>
+    where
+        T: 'static + PartialOrd + PartialEq + Clone + Display,
+    {
+        let result = self.parameter_box.add::<T>(name);
+        self.push_error(result);
+        ParameterEntryBuilder {
+            builder: self,
+            name: name.to_string(),
+            phantom: PhantomData,
+        }
+    }
+
+    /// Returns the parameter box, or an error combining all errors which occurred while building.
+    pub fn build(self) -> Result<ParameterBox, ParameterBoxError> {
+        if self.errors.is_empty() {
+            Ok(self.parameter_box)
+        } else {
+            Err(ParameterBoxError::InvalidCondition(
+                self.errors
+                    .iter()
+                    .map(|err| err.to_string())
+                    .collect::<Vec<String>>()
+                    .join("\n"),
+            ))
+        }
+    }
+
+    fn push_error(&mut self, result: Result<(), ParameterBoxError>) {
+        if let Err(err) = result {
+            self.errors.push(err);
+        }
+    }
+}
+
+impl<T> ParameterEntryBuilder<T>
+where
+    T: 'static + PartialOrd + PartialEq + Clone + Display,
+{
+    /// Sets the value.
+    #[track_caller]
+    pub fn value(mut self, value: T) -> Self {
+        let result = self.builder.parameter_box.set_value(&self.name, value);
+        self.builder.push_error(result);
+        self
+    }
+
+    /// Sets the range which is open at both ends.
+    #[track_caller]
+    pub fn range_open_open(mut self, min_limit: T, max_limit: T) -> Self {
+        let result = self
+            .builder
+            .parameter_box
+            .set_range_open_open(&self.name, (min_limit, max_limit));
+        self.builder.push_error(result);
+        self
+    }
+
+    /// Sets the range which is open at the minimum and closed at the maximum.
+    #[track_caller]
+    pub fn range_open_close(mut self, min_limit: T, max_limit: T) -> Self {
+        let result = self
+            .builder
+            .parameter_box
+            .set_range_open_close(&self.name, (min_limit, max_limit));
+        self.builder.push_error(result);
+        self
+    }
+
+    /// Sets the range which is closed at the minimum and open at the maximum.
+    #[track_caller]
+    pub fn range_close_open(mut self, min_limit: T, max_limit: T) -> Self {
+        let result = self
+            .builder
+            .parameter_box
+            .set_range_close_open(&self.name, (min_limit, max_limit));
+        self.builder.push_error(result);
+        self
+    }
+
+    /// Sets the range which is closed at both ends.
+    #[track_caller]
+    pub fn range_close_close(mut self, min_limit: T, max_limit: T) -> Self {
+        let result = self
+            .builder
+            .parameter_box
+            .set_range_close_close(&self.name, (min_limit, max_limit));
+        self.builder.push_error(result);
+        self
+    }
+
+    /// Sets the whitelist.
+    #[track_caller]
+    pub fn whitelist(mut self, whitelist: Vec<T>) -> Self {
+        let result = self
+            .builder
+            .parameter_box
+            .set_whitelist(&self.name, whitelist);
+        self.builder.push_error(result);
+        self
+    }
+
+    /// Sets the blacklist.
+    #[track_caller]
+    pub fn blacklist(mut self, blacklist: Vec<T>) -> Self {
+        let result = self
+            .builder
+            .parameter_box
+            .set_blacklist(&self.name, blacklist);
+        self.builder.push_error(result);
+        self
+    }
+
+    /// Sets the explanation.
+    #[track_caller]
+    pub fn explanation(mut self, explanation: &str) -> Self {
+        let result = self
+            .builder
+            .parameter_box
+            .set_explanation(&self.name, explanation.to_string());
+        self.builder.push_error(result);
+        self
+    }
+
+    /// Makes the parameter invisible.
+    #[deprecated(note = "use `invisible` instead")]
+    #[track_caller]
+    pub fn unvisible(self) -> Self {
+        self.invisible()
+    }
+
+    /// Makes the parameter invisible.
+    #[track_caller]
+    pub fn invisible(mut self) -> Self {
+        let result = self.builder.parameter_box.set_invisible(&self.name);
+        self.builder.push_error(result);
+        self
+    }
+
+    /// Finishes this parameter and returns to the builder of the parameter box.
+    pub fn done(self) -> ParameterBoxBuilder {
+        self.builder
+    }
+}
@@ -0,0 +1,87 @@
+// Copyright (c) 2021 Yuichi Ishida
+//
+// Released under the MIT license.
+// see https://opensource.org/licenses/mit-license.php
+
+/// Separator between a name and a value in a parameter file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Separator {
+    /// `<name> <value>`
+    #[default]
+    Whitespace,
+    /// `<name> = <value>`
+    Equals,
+    /// `<name>: <value>`
+    Colon,
+    /// Any of the above.
+    Any,
+}
+
+impl Separator {
+    /// Splits a line into a name and the rest, which holds the value.
+    pub(crate) fn split_line<'a>(&self, line: &'a str) -> (&'a str, &'a str) {
+        let is_separator = |c: char| match self {
+            Separator::Whitespace => c.is_whitespace(),
+            Separator::Equals => c == '=',
+            Separator::Colon => c == ':',
+            Separator::Any => c.is_whitespace() || c == '=' || c == ':',
+        };
+        match line.find(is_separator) {
+            Some(index) => {
+                let rest = line[index..].trim_start();
+                let rest = match self {
+                    Separator::Whitespace => rest,
+                    Separator::Equals => rest.strip_prefix('=').unwrap_or(rest),
+                    Separator::Colon => rest.strip_prefix(':').unwrap_or(rest),
+                    Separator::Any => rest.strip_prefix(|c| c == '=' || c == ':').unwrap_or(rest),
+                };
+                (line[..index].trim_end(), rest.trim_start())
+            }
+            None => (line, ""),
+        }
+    }
+
+    /// Returns the line format used in error messages.
+    pub(crate) fn line_format(&self) -> &'static str {
+        match self {
+            Separator::Whitespace => "'<name> <value>'",
+            Separator::Equals => "'<name> = <value>'",
+            Separator::Colon => "'<name>: <value>'",
+            Separator::Any => "'<name> <value>', '<name> = <value>' or '<name>: <value>'",
+        }
+    }
+}
+
+/// Options of `ParameterBox::read_file_with_options`.
+///
+/// ```
+/// use parameter_box::{ParameterFileOptions, Separator};
+///
+/// let options = ParameterFileOptions::new()
+///     .separator(Separator::Equals)
+///     .skip_unknown_names(true);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ParameterFileOptions {
+    pub(crate) separator: Separator,
+    pub(crate) skip_unknown_names: bool,
+}
+
+impl ParameterFileOptions {
+    /// Creates the options used by `ParameterBox::read_file`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the separator between names and values.
+    pub fn separator(mut self, separator: Separator) -> Self {
+        self.separator = separator;
+        self
+    }
+
+    /// Skips names which have not been added instead of reporting them as errors.
+    pub fn skip_unknown_names(mut self, skip_unknown_names: bool) -> Self {
+        self.skip_unknown_names = skip_unknown_names;
+        self
+    }
+}
@@ -3,34 +3,168 @@
 // Released under the MIT license.
 // see https://opensource.org/licenses/mit-license.php
 
+mod builder;
+mod file_options;
+mod numeric;
 mod parameter;
 
 use core::fmt::Display;
 use core::str::FromStr;
+use indexmap::IndexMap;
+use std::any::Any;
 use std::collections::HashMap;
 use std::fs::File;
 use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::rc::Rc;
+use std::sync::Arc;
 
 #[cfg(debug_assertions)]
 use std::panic::Location;
 
-use parameter::{ListCondition, ListError, Parameter, ParameterCore, RangeCondition, RangeError};
+pub use builder::{ParameterBoxBuilder, ParameterEntryBuilder};
+pub use file_options::{ParameterFileOptions, Separator};
+pub use numeric::{Float, Numeric};
+pub use parameter::{ListCondition, RangeCondition};
+pub use sealed::SealedParameterBox;
 
-#[derive(Debug)]
+#[cfg(feature = "derive")]
+pub use parameter_box_derive::ParameterBoxFrom;
+
+use parameter::{ListError, Parameter, ParameterCore, RangeError, Validator};
+
+#[derive(Debug, Clone)]
 pub struct ParameterBox {
-    parameter_list: HashMap<String, Parameter>,
-    added_order: Vec<String>,
+    parameter_list: IndexMap<String, Parameter>,
     error_counter: u32,
+    lenient_cli_args: bool,
+    full_validation: bool,
+    cross_constraints: Vec<CrossConstraint>,
+    warnings: Vec<String>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum ParameterBoxError {
     InvalidCondition(String),
     AlreadyAdded(String),
     NotAdded(String),
     InvalidParse(String),
     InvalidInputFile(String),
-    IoError(String),
+    /// Formatted message and the original I/O error, which is shared so that errors can be cloned.
+    IoError(String, Arc<std::io::Error>),
+    HelpRequested(String),
+    /// The parameter with this name does not have a value.
+    NotSet(String),
+    /// The parameter is accessed with a type other than the one it was added with.
+    TypeMismatch {
+        name: String,
+        requested: String,
+        actual: String,
+    },
+}
+
+/// Description of a parameter whose type is given by its name, e.g. `"f64"` or `"String"`.
+#[derive(Debug, Clone)]
+pub struct TypeErasedParam {
+    pub name: String,
+    pub type_name: String,
+    pub default_value: Option<String>,
+    pub explanation: Option<String>,
+}
+
+/// Read-only view of a parameter, which is yielded by `ParameterBox::iter`.
+#[derive(Debug, Clone, Copy)]
+pub struct ParameterView<'a> {
+    pub type_name: &'a str,
+    pub value_string: Option<&'a str>,
+    pub range_string: (Option<&'a str>, Option<&'a str>),
+    pub list_string: Option<(&'a str, &'a str)>,
+    pub explanation: Option<&'a str>,
+    pub visible: bool,
+    pub required: bool,
+}
+
+impl<'a> ParameterView<'a> {
+    fn new(parameter: &'a Parameter) -> Self {
+        Self {
+            type_name: &parameter.type_string,
+            value_string: parameter.value_string.as_deref(),
+            range_string: (
+                parameter.range_string.0.as_deref(),
+                parameter.range_string.1.as_deref(),
+            ),
+            list_string: parameter
+                .list_string
+                .as_ref()
+                .map(|(list_type, list)| (list_type.as_str(), list.as_str())),
+            explanation: parameter.explanation.as_deref(),
+            visible: !parameter.unvisible,
+            required: parameter.required,
+        }
+    }
+}
+
+/// Metadata of a single parameter, which is returned by `ParameterBox::parameter_info`.
+///
+/// `list_display` holds the listed values, e.g. `"[1, 2, 3]"`, of either a whitelist or a blacklist.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParameterInfo<'a> {
+    pub name: &'a str,
+    pub type_name: &'a str,
+    pub value_string: Option<&'a str>,
+    pub range_min: Option<&'a str>,
+    pub range_max: Option<&'a str>,
+    pub list_display: Option<&'a str>,
+    pub explanation: Option<&'a str>,
+    pub visible: bool,
+    pub required: bool,
+}
+
+impl<'a> ParameterInfo<'a> {
+    fn new(name: &'a str, parameter: &'a Parameter) -> Self {
+        Self {
+            name,
+            type_name: &parameter.type_string,
+            value_string: parameter.value_string.as_deref(),
+            range_min: parameter.range_string.0.as_deref(),
+            range_max: parameter.range_string.1.as_deref(),
+            list_display: parameter
+                .list_string
+                .as_ref()
+                .map(|(_, list)| list.as_str()),
+            explanation: parameter.explanation.as_deref(),
+            visible: !parameter.unvisible,
+            required: parameter.required,
+        }
+    }
+}
+
+/// Constraint on several parameters registered by `add_cross_constraint`.
+#[derive(Clone)]
+struct CrossConstraint {
+    description: String,
+    involved: Vec<String>,
+    validator: Rc<CrossConstraintFn>,
+}
+
+type CrossConstraintFn = dyn Fn(&ParameterBox) -> Result<(), String>;
+
+impl core::fmt::Debug for CrossConstraint {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("CrossConstraint")
+            .field("description", &self.description)
+            .field("involved", &self.involved)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Invokes `$macro_name` with the list of types which can be read from strings.
+macro_rules! with_readable_types {
+    ($macro_name:ident) => {
+        $macro_name!(
+            bool, u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64, char,
+            String
+        )
+    };
 }
 
 #[cfg(debug_assertions)]
@@ -67,6 +201,28 @@ macro_rules! err_msg_not_added {
     };
 }
 
+macro_rules! err_msg_passthrough {
+    ($name:expr) => {
+        format!(
+            "{0} `{1}` is a passthrough parameter, so constraints cannot be set.",
+            err_msg_header!(),
+            $name
+        )
+    };
+}
+
+macro_rules! err_msg_rejected_by_validator {
+    ($name:expr, $value:expr, $msg:expr) => {
+        &format!(
+            "{0} `{1}` = {2} is rejected by the validator: {3}",
+            err_msg_header!(),
+            $name,
+            $value,
+            $msg,
+        )
+    };
+}
+
 macro_rules! err_msg_bad_condition {
     ($name:expr, $value:expr,$condition:expr) => {
         &format!(
@@ -79,74 +235,96 @@ macro_rules! err_msg_bad_condition {
     };
 }
 
+#[cfg(feature = "regex")]
+mod regex_constraint;
+mod sealed;
+#[cfg(feature = "serde")]
+mod serde_support;
+#[cfg(feature = "toml")]
+mod toml_file;
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __match_result {
+    ($result:expr, $err_msg:ident => $on_error:expr, $help_msg:ident => $on_help:expr) => {
+        match $result {
+            Ok(x) => x,
+            Err($crate::ParameterBoxError::InvalidCondition($err_msg)) => $on_error,
+            Err($crate::ParameterBoxError::AlreadyAdded($err_msg)) => $on_error,
+            Err($crate::ParameterBoxError::NotAdded($err_msg)) => $on_error,
+            Err($crate::ParameterBoxError::InvalidParse($err_msg)) => $on_error,
+            Err($crate::ParameterBoxError::InvalidInputFile($err_msg)) => $on_error,
+            Err($crate::ParameterBoxError::IoError($err_msg, _)) => $on_error,
+            Err($crate::ParameterBoxError::HelpRequested($help_msg)) => $on_help,
+            Err(err @ $crate::ParameterBoxError::NotSet(_))
+            | Err(err @ $crate::ParameterBoxError::TypeMismatch { .. }) => {
+                let $err_msg = err.to_string();
+                $on_error
+            }
+        }
+    };
+}
+
 #[macro_export]
 macro_rules! unwrap_result{
     ($parameter_box:ident . $func:ident :: <$type:ty> ( $($args:expr),* ))=>{
-        match $parameter_box.$func::<$type>($($args),*){
-            Ok(x) => x,
-            Err(ParameterBoxError::InvalidCondition(err_msg)) => {
-                eprintln!("{}", err_msg);
-                std::process::exit(1);
-            },
-            Err(ParameterBoxError::AlreadyAdded(err_msg)) => {
-                eprintln!("{}", err_msg);
-                std::process::exit(1);
-            },
-            Err(ParameterBoxError::NotAdded(err_msg)) => {
-                eprintln!("{}", err_msg);
-                std::process::exit(1);
-            },
-            Err(ParameterBoxError::InvalidParse(err_msg)) => {
-                eprintln!("{}", err_msg);
-                std::process::exit(1);
-            },
-            Err(ParameterBoxError::InvalidInputFile(err_msg)) => {
+        $crate::__match_result!(
+            $parameter_box.$func::<$type>($($args),*),
+            err_msg => {
                 eprintln!("{}", err_msg);
-                std::process::exit(1);
-            },
-            Err(ParameterBoxError::IoError(io_error)) => {
-                eprintln!("{}", io_error);
-                std::process::exit(1);
+                std::process::exit(1)
             },
-        }
+            _help_msg => std::process::exit(0)
+        )
     };
     ($parameter_box:ident . $func:ident ( $($args:expr),* ))=>{
-        match $parameter_box.$func($($args),*){
-            Ok(x) => x,
-            Err(ParameterBoxError::InvalidCondition(err_msg)) => {
-                eprintln!("{}", err_msg);
-                std::process::exit(1);
-            },
-            Err(ParameterBoxError::AlreadyAdded(err_msg)) => {
-                eprintln!("{}", err_msg);
-                std::process::exit(1);
-            },
-            Err(ParameterBoxError::NotAdded(err_msg)) => {
-                eprintln!("{}", err_msg);
-                std::process::exit(1);
-            },
-            Err(ParameterBoxError::InvalidParse(err_msg)) => {
-                eprintln!("{}", err_msg);
-                std::process::exit(1);
-            },
-            Err(ParameterBoxError::InvalidInputFile(err_msg)) => {
+        $crate::__match_result!(
+            $parameter_box.$func($($args),*),
+            err_msg => {
                 eprintln!("{}", err_msg);
-                std::process::exit(1);
-            },
-            Err(ParameterBoxError::IoError(io_error)) => {
-                eprintln!("{}", io_error);
-                std::process::exit(1);
+                std::process::exit(1)
             },
-        }
+            _help_msg => std::process::exit(0)
+        )
+    };
+}
+
+/// Same as `unwrap_result!`, but panics with the error message instead of exiting the process.
+#[macro_export]
+macro_rules! panic_result{
+    ($parameter_box:ident . $func:ident :: <$type:ty> ( $($args:expr),* ))=>{
+        $crate::__match_result!(
+            $parameter_box.$func::<$type>($($args),*),
+            err_msg => panic!("{}", err_msg),
+            help_msg => panic!("{}", help_msg)
+        )
+    };
+    ($parameter_box:ident . $func:ident ( $($args:expr),* ))=>{
+        $crate::__match_result!(
+            $parameter_box.$func($($args),*),
+            err_msg => panic!("{}", err_msg),
+            help_msg => panic!("{}", help_msg)
+        )
     };
 }
 
 impl ParameterBox {
     pub fn new() -> Self {
         Self {
-            parameter_list: HashMap::<String, Parameter>::new(),
-            added_order: Vec::<String>::new(),
+            parameter_list: IndexMap::<String, Parameter>::new(),
             error_counter: 0,
+            lenient_cli_args: false,
+            full_validation: false,
+            cross_constraints: Vec::new(),
+            warnings: Vec::new(),
+        }
+    }
+
+    /// Creates an empty parameter box with storage for at least `capacity` parameters.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            parameter_list: IndexMap::<String, Parameter>::with_capacity(capacity),
+            ..Self::new()
         }
     }
 
@@ -161,12 +339,252 @@ impl ParameterBox {
                 name
             )))
         } else {
-            let name_string = name.to_string();
             self.parameter_list
-                .insert(name_string.clone(), Parameter::new::<T>());
-            self.added_order.push(name_string);
+                .insert(name.to_string(), Parameter::new::<T>());
+            Ok(())
+        }
+    }
+
+    /// Adds a `bool` parameter whose value is `false`.
+    #[track_caller]
+    pub fn add_flag(&mut self, name: &str) -> Result<(), ParameterBoxError> {
+        self.add_with_value(name, false)
+    }
+
+    /// Adds a `bool` parameter whose value is `true`.
+    #[track_caller]
+    pub fn add_flag_on(&mut self, name: &str) -> Result<(), ParameterBoxError> {
+        self.add_with_value(name, true)
+    }
+
+    /// Adds a parameter whose type is given by `desc.type_name`.
+    ///
+    /// Both short names such as `"String"` and full names such as `"alloc::string::String"` are
+    /// accepted. Only types which can be read from files are supported.
+    #[track_caller]
+    pub fn add_from_type_erased(&mut self, desc: TypeErasedParam) -> Result<(), ParameterBoxError> {
+        let mut added = false;
+        macro_rules! add_by_type_name {
+            ($($type:ty),+) => {
+                $(
+                    if !added
+                        && (desc.type_name == stringify!($type)
+                            || desc.type_name == std::any::type_name::<$type>())
+                    {
+                        self.add::<$type>(&desc.name)?;
+                        added = true;
+                    }
+                )+
+            };
+        }
+        with_readable_types!(add_by_type_name);
+        if !added {
+            self.error_counter += 1;
+            return Err(ParameterBoxError::InvalidParse(format!(
+                "{} the type `{}` of `{}` is not supported.",
+                err_msg_header!(),
+                desc.type_name,
+                desc.name
+            )));
+        }
+        if let Some(explanation) = desc.explanation {
+            self.set_explanation(&desc.name, explanation)?;
+        }
+        if let Some(default_value) = &desc.default_value {
+            self.set_value_auto_from_string(&desc.name, default_value)?;
+        }
+        Ok(())
+    }
+
+    /// Removes a parameter together with the cross constraints involving it.
+    #[track_caller]
+    pub fn remove(&mut self, name: &str) -> Result<(), ParameterBoxError> {
+        if self.parameter_list.shift_remove(name).is_some() {
+            self.cross_constraints
+                .retain(|cross_constraint| !cross_constraint.involved.iter().any(|x| x == name));
             Ok(())
+        } else {
+            self.error_counter += 1;
+            Err(ParameterBoxError::NotAdded(err_msg_not_added!(name)))
+        }
+    }
+
+    /// Renames a parameter, keeping its type, value, constraints and explanation.
+    ///
+    /// Validators of cross constraints keep working; they see the parameter under the old name.
+    #[track_caller]
+    pub fn rename(&mut self, old_name: &str, new_name: &str) -> Result<(), ParameterBoxError> {
+        if !self.parameter_list.contains_key(old_name) {
+            self.error_counter += 1;
+            return Err(ParameterBoxError::NotAdded(err_msg_not_added!(old_name)));
+        }
+        if self.parameter_list.contains_key(new_name) {
+            self.error_counter += 1;
+            return Err(ParameterBoxError::AlreadyAdded(err_msg_already_added!(
+                new_name
+            )));
+        }
+        let (index, _, parameter) = self.parameter_list.shift_remove_full(old_name).unwrap();
+        self.parameter_list
+            .shift_insert(index, new_name.to_string(), parameter);
+        for name in self
+            .cross_constraints
+            .iter_mut()
+            .flat_map(|cross_constraint| cross_constraint.involved.iter_mut())
+            .filter(|name| *name == old_name)
+        {
+            *name = new_name.to_string();
+        }
+        for cross_constraint in self.cross_constraints.iter_mut() {
+            let validator = Rc::clone(&cross_constraint.validator);
+            let old_name = old_name.to_string();
+            let new_name = new_name.to_string();
+            cross_constraint.validator = Rc::new(move |parameter_box: &ParameterBox| {
+                validator(&parameter_box.with_name_restored(&new_name, &old_name))
+            });
+        }
+        Ok(())
+    }
+
+    /// Returns a copy of the parameters where `new_name` is renamed back to `old_name`.
+    fn with_name_restored(&self, new_name: &str, old_name: &str) -> ParameterBox {
+        let mut parameter_box = ParameterBox::new();
+        for (name, parameter) in self.parameter_list.iter() {
+            if name == new_name {
+                parameter_box
+                    .parameter_list
+                    .insert(old_name.to_string(), parameter.clone());
+            } else if name != old_name {
+                parameter_box
+                    .parameter_list
+                    .insert(name.clone(), parameter.clone());
+            }
+        }
+        parameter_box
+    }
+
+    /// Makes a parameter unset, keeping its constraints.
+    #[track_caller]
+    pub fn clear_value(&mut self, name: &str) -> Result<(), ParameterBoxError> {
+        if let Some(parameter) = self.parameter_list.get_mut(name) {
+            parameter.parameter_core = parameter.parameter_core.without_value();
+            parameter.value_string = None;
+            Ok(())
+        } else {
+            self.error_counter += 1;
+            Err(ParameterBoxError::NotAdded(err_msg_not_added!(name)))
+        }
+    }
+
+    /// Makes all parameters unset, keeping their constraints.
+    pub fn reset_all_values(&mut self) {
+        for parameter in self.parameter_list.values_mut() {
+            parameter.parameter_core = parameter.parameter_core.without_value();
+            parameter.value_string = None;
+        }
+    }
+
+    /// Returns a copy of the parameter box whose parameter names are prefixed with `prefix.`.
+    ///
+    /// Cross constraints are carried over; their validators see the parameters without the prefix.
+    pub fn with_name_prefix(&self, prefix: &str) -> ParameterBox {
+        let mut parameter_box = ParameterBox::new();
+        for (name, parameter) in self.parameter_list.iter() {
+            parameter_box
+                .parameter_list
+                .insert(format!("{}.{}", prefix, name), parameter.clone());
+        }
+        for cross_constraint in self.cross_constraints.iter() {
+            let validator = Rc::clone(&cross_constraint.validator);
+            let prefix = prefix.to_string();
+            parameter_box.cross_constraints.push(CrossConstraint {
+                description: cross_constraint.description.clone(),
+                involved: cross_constraint
+                    .involved
+                    .iter()
+                    .map(|name| format!("{}.{}", prefix, name))
+                    .collect(),
+                validator: Rc::new(move |parameter_box: &ParameterBox| {
+                    validator(&parameter_box.without_name_prefix(&prefix))
+                }),
+            });
         }
+        parameter_box.error_counter = self.error_counter;
+        parameter_box.lenient_cli_args = self.lenient_cli_args;
+        parameter_box.full_validation = self.full_validation;
+        parameter_box.warnings = self.warnings.clone();
+        parameter_box
+    }
+
+    /// Returns a copy of the parameters whose names start with `prefix.`, with the prefix removed.
+    fn without_name_prefix(&self, prefix: &str) -> ParameterBox {
+        let prefix = format!("{}.", prefix);
+        let mut parameter_box = ParameterBox::new();
+        for (name, parameter) in self.parameter_list.iter() {
+            if let Some(name) = name.strip_prefix(&prefix) {
+                parameter_box
+                    .parameter_list
+                    .insert(name.to_string(), parameter.clone());
+            }
+        }
+        parameter_box
+    }
+
+    /// Adds a parameter and sets its value, which is remembered as the default value.
+    #[track_caller]
+    pub fn add_with_default<T>(&mut self, name: &str, default: T) -> Result<(), ParameterBoxError>
+    where
+        T: 'static + PartialOrd + PartialEq + Clone + Display,
+    {
+        self.add::<T>(name)?;
+        let result = self.set_value::<T>(name, default);
+        let parameter = self.parameter_list.get_mut(name).unwrap();
+        parameter.default_string = parameter.value_string.clone();
+        result
+    }
+
+    /// Adds parameters of type `T` in the order of `names`.
+    ///
+    /// Names which are already added are skipped and reported together in one `AlreadyAdded`.
+    #[track_caller]
+    pub fn add_many<T>(&mut self, names: &[&str]) -> Result<(), ParameterBoxError>
+    where
+        T: 'static + PartialOrd + PartialEq + Clone + Display,
+    {
+        let mut error_sequence = false;
+        let mut err_msg = String::new();
+        for name in names {
+            if let Err(ParameterBoxError::AlreadyAdded(msg)) = self.add::<T>(name) {
+                ParameterBox::sequence_err_or_not(&mut error_sequence, &mut err_msg);
+                err_msg.push_str(&msg);
+            }
+        }
+        if err_msg.is_empty() {
+            Ok(())
+        } else {
+            Err(ParameterBoxError::AlreadyAdded(err_msg))
+        }
+    }
+
+    /// Adds a parameter and sets its value.
+    #[track_caller]
+    pub fn add_with_value<T>(&mut self, name: &str, value: T) -> Result<(), ParameterBoxError>
+    where
+        T: 'static + PartialOrd + PartialEq + Clone + Display,
+    {
+        self.add::<T>(name)?;
+        self.set_value::<T>(name, value)
+    }
+
+    /// Adds a parameter which accepts any value; constraints cannot be set on it.
+    #[track_caller]
+    pub fn add_passthrough<T>(&mut self, name: &str) -> Result<(), ParameterBoxError>
+    where
+        T: 'static + PartialOrd + PartialEq + Clone + Display,
+    {
+        self.add::<T>(name)?;
+        self.parameter_list.get_mut(name).unwrap().passthrough = true;
+        Ok(())
     }
 
     #[track_caller]
@@ -177,14 +595,13 @@ impl ParameterBox {
         if let Some(parameter) = self.parameter_list.get_mut(name) {
             let mut error_sequence = false;
             let mut err_msg = String::new();
-            let mut new_parameter_core = Box::new(
-                parameter
-                    .parameter_core
-                    .as_ref()
-                    .downcast_ref::<ParameterCore<T>>()
-                    .expect("Downcast failed.")
-                    .clone(),
-            );
+            let mut new_parameter_core = match ParameterBox::downcast_core::<T>(name, parameter) {
+                Ok(parameter_core) => Box::new(parameter_core.clone()),
+                Err(err) => {
+                    self.error_counter += 1;
+                    return Err(err);
+                }
+            };
             new_parameter_core.value = Some(value);
             let value = new_parameter_core.value.as_ref().unwrap();
             if let Err(RangeError::LessThanMinLimit(condition)) =
@@ -214,6 +631,36 @@ impl ParameterBox {
                 }
                 Ok(()) => (),
             }
+            if let Err(condition) = new_parameter_core.check_nonzero() {
+                self.error_counter += 1;
+                ParameterBox::sequence_err_or_not(&mut error_sequence, &mut err_msg);
+                err_msg.push_str(err_msg_bad_condition!(name, value, condition));
+            }
+            if let Err(condition) = new_parameter_core.check_finite() {
+                self.error_counter += 1;
+                ParameterBox::sequence_err_or_not(&mut error_sequence, &mut err_msg);
+                err_msg.push_str(err_msg_bad_condition!(name, value, condition));
+            }
+            if let Some(value) = (value as &dyn Any).downcast_ref::<String>() {
+                for condition in ParameterBox::violated_string_conditions(parameter, value) {
+                    self.error_counter += 1;
+                    ParameterBox::sequence_err_or_not(&mut error_sequence, &mut err_msg);
+                    err_msg.push_str(err_msg_bad_condition!(name, value, condition));
+                }
+            }
+            if let (true, Some(validator)) = (err_msg.is_empty(), &parameter.validator) {
+                if let Err(msg) = (validator.0)(value) {
+                    self.error_counter += 1;
+                    ParameterBox::sequence_err_or_not(&mut error_sequence, &mut err_msg);
+                    err_msg.push_str(err_msg_rejected_by_validator!(name, value, msg));
+                }
+            }
+            if let (true, Some(warning_range)) = (err_msg.is_empty(), &parameter.warning_range) {
+                if let Err(condition) = (warning_range.0)(value) {
+                    self.warnings
+                        .push(ParameterBox::warning_msg(name, value, &condition));
+                }
+            }
             parameter.value_string = Some(format!("{}", value));
             parameter.parameter_core = new_parameter_core;
             if err_msg.is_empty() {
@@ -227,6 +674,62 @@ impl ParameterBox {
         }
     }
 
+    /// Sets a value only when `name` does not have a value yet.
+    #[track_caller]
+    pub fn set_value_if_unset<T>(&mut self, name: &str, value: T) -> Result<(), ParameterBoxError>
+    where
+        T: 'static + PartialOrd + PartialEq + Clone + Display,
+    {
+        match self.parameter_list.get(name) {
+            Some(parameter) if parameter.value_string.is_some() => Ok(()),
+            _ => self.set_value(name, value),
+        }
+    }
+
+    /// Sets a validator which values must pass after satisfying the range and the list.
+    ///
+    /// The validator replaces the previous one and is also applied to the current value.
+    #[track_caller]
+    pub fn set_validator<T, F>(&mut self, name: &str, validator: F) -> Result<(), ParameterBoxError>
+    where
+        T: 'static + PartialOrd + PartialEq + Clone + Display,
+        F: Fn(&T) -> Result<(), String> + 'static,
+    {
+        if let Some(parameter) = self.parameter_list.get_mut(name) {
+            if parameter.passthrough {
+                self.error_counter += 1;
+                return Err(ParameterBoxError::InvalidCondition(err_msg_passthrough!(
+                    name
+                )));
+            }
+            let value = match ParameterBox::downcast_core::<T>(name, parameter) {
+                Ok(parameter_core) => parameter_core.value.clone(),
+                Err(err) => {
+                    self.error_counter += 1;
+                    return Err(err);
+                }
+            };
+            let validator = Validator(Rc::new(move |value: &dyn Any| {
+                validator(value.downcast_ref::<T>().expect("Downcast failed."))
+            }));
+            let result = match &value {
+                Some(value) => (validator.0)(value),
+                None => Ok(()),
+            };
+            parameter.validator = Some(validator);
+            if let Err(msg) = result {
+                self.error_counter += 1;
+                return Err(ParameterBoxError::InvalidCondition(
+                    err_msg_rejected_by_validator!(name, value.unwrap(), msg).to_string(),
+                ));
+            }
+            Ok(())
+        } else {
+            self.error_counter += 1;
+            Err(ParameterBoxError::NotAdded(err_msg_not_added!(name)))
+        }
+    }
+
     #[track_caller]
     pub fn set_range_open_open<T>(
         &mut self,
@@ -296,16 +799,172 @@ impl ParameterBox {
         )
     }
 
+    /// Sets an advisory range.
+    ///
+    /// Values outside it are still accepted, but a message is appended to `get_warnings`.
     #[track_caller]
-    pub fn set_min_limit_open<T>(
+    pub fn set_warning_range_open_open<T>(
         &mut self,
         name: &str,
-        min_limit: T,
+        range: (T, T),
     ) -> Result<(), ParameterBoxError>
     where
         T: 'static + PartialOrd + PartialEq + Clone + Display,
     {
-        self.set_min_limit(name, RangeCondition::Open(min_limit))
+        self.set_warning_range(
+            name,
+            (RangeCondition::Open(range.0), RangeCondition::Open(range.1)),
+        )
+    }
+
+    /// Sets an advisory range which is open at the minimum and closed at the maximum.
+    ///
+    /// See `set_warning_range_open_open`.
+    #[track_caller]
+    pub fn set_warning_range_open_close<T>(
+        &mut self,
+        name: &str,
+        range: (T, T),
+    ) -> Result<(), ParameterBoxError>
+    where
+        T: 'static + PartialOrd + PartialEq + Clone + Display,
+    {
+        self.set_warning_range(
+            name,
+            (
+                RangeCondition::Open(range.0),
+                RangeCondition::Close(range.1),
+            ),
+        )
+    }
+
+    /// Sets an advisory range which is closed at the minimum and open at the maximum.
+    ///
+    /// See `set_warning_range_open_open`.
+    #[track_caller]
+    pub fn set_warning_range_close_open<T>(
+        &mut self,
+        name: &str,
+        range: (T, T),
+    ) -> Result<(), ParameterBoxError>
+    where
+        T: 'static + PartialOrd + PartialEq + Clone + Display,
+    {
+        self.set_warning_range(
+            name,
+            (
+                RangeCondition::Close(range.0),
+                RangeCondition::Open(range.1),
+            ),
+        )
+    }
+
+    /// Sets an advisory range which is closed at both ends.
+    ///
+    /// See `set_warning_range_open_open`.
+    #[track_caller]
+    pub fn set_warning_range_close_close<T>(
+        &mut self,
+        name: &str,
+        range: (T, T),
+    ) -> Result<(), ParameterBoxError>
+    where
+        T: 'static + PartialOrd + PartialEq + Clone + Display,
+    {
+        self.set_warning_range(
+            name,
+            (
+                RangeCondition::Close(range.0),
+                RangeCondition::Close(range.1),
+            ),
+        )
+    }
+
+    /// Returns the warnings accumulated by values outside warning ranges.
+    pub fn get_warnings(&self) -> &[String] {
+        &self.warnings
+    }
+
+    /// Removes the warnings returned by `get_warnings`.
+    pub fn clear_warnings(&mut self) {
+        self.warnings.clear();
+    }
+
+    /// Sets the range parsed from `min_str` and `max_str`.
+    ///
+    /// The range is open at both ends if `open_open` is true, and closed at both ends otherwise.
+    #[track_caller]
+    pub fn set_range_from_str<T>(
+        &mut self,
+        name: &str,
+        min_str: &str,
+        max_str: &str,
+        open_open: bool,
+    ) -> Result<(), ParameterBoxError>
+    where
+        T: 'static + PartialOrd + PartialEq + Clone + Display + FromStr,
+    {
+        let mut min_limit = None;
+        let mut max_limit = None;
+        let mut error_sequence = false;
+        let mut err_msg = String::new();
+        for (limit_str, limit) in [(min_str, &mut min_limit), (max_str, &mut max_limit)] {
+            match T::from_str(limit_str) {
+                Ok(value) => *limit = Some(value),
+                Err(_) => {
+                    self.error_counter += 1;
+                    ParameterBox::sequence_err_or_not(&mut error_sequence, &mut err_msg);
+                    err_msg.push_str(&format!(
+                        "{} the limit {} of `{}` cannot be parsed as {}.",
+                        err_msg_header!(),
+                        limit_str,
+                        name,
+                        std::any::type_name::<T>(),
+                    ));
+                }
+            }
+        }
+        match (min_limit, max_limit) {
+            (Some(min_limit), Some(max_limit)) if open_open => {
+                self.set_range_open_open(name, (min_limit, max_limit))
+            }
+            (Some(min_limit), Some(max_limit)) => {
+                self.set_range_close_close(name, (min_limit, max_limit))
+            }
+            _ => Err(ParameterBoxError::InvalidParse(err_msg)),
+        }
+    }
+
+    /// Sets the closed range only when `name` does not have a value yet.
+    ///
+    /// Returns `Ok(true)` if the range is set and `Ok(false)` if it is skipped.
+    #[track_caller]
+    pub fn try_set_range_close_close<T>(
+        &mut self,
+        name: &str,
+        range: (T, T),
+    ) -> Result<bool, ParameterBoxError>
+    where
+        T: 'static + PartialOrd + PartialEq + Clone + Display,
+    {
+        if let Some(parameter) = self.parameter_list.get(name) {
+            if parameter.value_string.is_some() {
+                return Ok(false);
+            }
+        }
+        self.set_range_close_close(name, range).map(|()| true)
+    }
+
+    #[track_caller]
+    pub fn set_min_limit_open<T>(
+        &mut self,
+        name: &str,
+        min_limit: T,
+    ) -> Result<(), ParameterBoxError>
+    where
+        T: 'static + PartialOrd + PartialEq + Clone + Display,
+    {
+        self.set_min_limit(name, RangeCondition::Open(min_limit))
     }
 
     #[track_caller]
@@ -344,49 +1003,171 @@ impl ParameterBox {
         self.set_max_limit(name, RangeCondition::Close(max_limit))
     }
 
+    /// Sets the minimum limit, keeping the maximum limit.
     #[track_caller]
-    pub fn set_blacklist<T>(
+    pub fn set_min_limit<T>(
         &mut self,
         name: &str,
-        blacklist: Vec<T>,
+        min_limit: RangeCondition<T>,
     ) -> Result<(), ParameterBoxError>
     where
         T: 'static + PartialOrd + PartialEq + Clone + Display,
     {
-        self.set_list_info(name, ListCondition::Black(blacklist))
+        if let Some(parameter) = self.parameter_list.get_mut(name) {
+            if parameter.passthrough {
+                self.error_counter += 1;
+                return Err(ParameterBoxError::InvalidCondition(err_msg_passthrough!(
+                    name
+                )));
+            }
+            let mut err_msg = String::new();
+            let mut new_parameter_core = match ParameterBox::downcast_core::<T>(name, parameter) {
+                Ok(parameter_core) => Box::new(parameter_core.clone()),
+                Err(err) => {
+                    self.error_counter += 1;
+                    return Err(err);
+                }
+            };
+            parameter.range_string.0 = ParameterBox::make_min_limit_string(&min_limit);
+            new_parameter_core.range.0 = Some(min_limit);
+            if let Some(value) = &new_parameter_core.value {
+                if let Err(RangeError::LessThanMinLimit(condition)) =
+                    new_parameter_core.check_min_limit()
+                {
+                    self.error_counter += 1;
+                    err_msg.push_str(err_msg_bad_condition!(name, value, condition));
+                }
+            }
+            parameter.parameter_core = new_parameter_core;
+            if err_msg.is_empty() {
+                Ok(())
+            } else {
+                Err(ParameterBoxError::InvalidCondition(err_msg))
+            }
+        } else {
+            self.error_counter += 1;
+            Err(ParameterBoxError::NotAdded(err_msg_not_added!(name)))
+        }
     }
 
+    /// Sets the maximum limit, keeping the minimum limit.
     #[track_caller]
-    pub fn set_whitelist<T>(
+    pub fn set_max_limit<T>(
         &mut self,
         name: &str,
-        whitelist: Vec<T>,
+        max_limit: RangeCondition<T>,
     ) -> Result<(), ParameterBoxError>
     where
         T: 'static + PartialOrd + PartialEq + Clone + Display,
     {
-        self.set_list_info(name, ListCondition::White(whitelist))
-    }
-
-    #[track_caller]
-    pub fn set_explanation(
-        &mut self,
-        name: &str,
-        explanation: String,
-    ) -> Result<(), ParameterBoxError> {
         if let Some(parameter) = self.parameter_list.get_mut(name) {
-            parameter.explanation = Some(explanation);
-            Ok(())
+            if parameter.passthrough {
+                self.error_counter += 1;
+                return Err(ParameterBoxError::InvalidCondition(err_msg_passthrough!(
+                    name
+                )));
+            }
+            let mut err_msg = String::new();
+            let mut new_parameter_core = match ParameterBox::downcast_core::<T>(name, parameter) {
+                Ok(parameter_core) => Box::new(parameter_core.clone()),
+                Err(err) => {
+                    self.error_counter += 1;
+                    return Err(err);
+                }
+            };
+            parameter.range_string.1 = ParameterBox::make_max_limit_string(&max_limit);
+            new_parameter_core.range.1 = Some(max_limit);
+            if let Some(value) = &new_parameter_core.value {
+                if let Err(RangeError::LargerThanMaxLimit(condition)) =
+                    new_parameter_core.check_max_limit()
+                {
+                    self.error_counter += 1;
+                    err_msg.push_str(err_msg_bad_condition!(name, value, condition));
+                }
+            }
+            parameter.parameter_core = new_parameter_core;
+            if err_msg.is_empty() {
+                Ok(())
+            } else {
+                Err(ParameterBoxError::InvalidCondition(err_msg))
+            }
         } else {
             self.error_counter += 1;
             Err(ParameterBoxError::NotAdded(err_msg_not_added!(name)))
         }
     }
 
+    /// Excludes zero from the values of a numeric parameter.
     #[track_caller]
-    pub fn set_unvisible(&mut self, name: &str) -> Result<(), ParameterBoxError> {
+    pub fn set_nonzero<T: Numeric>(&mut self, name: &str) -> Result<(), ParameterBoxError> {
+        self.set_core_constraint::<T, _, _>(
+            name,
+            format!("≠ {}", T::zero()),
+            |parameter_core| parameter_core.excluded_zero = Some(T::zero()),
+            ParameterCore::check_nonzero,
+        )
+    }
+
+    /// Rejects NaN and infinities as values of a floating-point parameter.
+    #[track_caller]
+    pub fn set_finite_only<T: Float>(&mut self, name: &str) -> Result<(), ParameterBoxError> {
+        self.set_core_constraint::<T, _, _>(
+            name,
+            "finite".to_string(),
+            |parameter_core| parameter_core.require_finite = Some(T::is_finite),
+            ParameterCore::check_finite,
+        )
+    }
+
+    /// Restricts a numeric parameter to values greater than zero.
+    #[track_caller]
+    pub fn set_positive<T: Numeric>(&mut self, name: &str) -> Result<(), ParameterBoxError> {
+        self.set_min_limit_open(name, T::zero())
+    }
+
+    /// Restricts a numeric parameter to values greater than or equal to zero.
+    #[track_caller]
+    pub fn set_nonnegative<T: Numeric>(&mut self, name: &str) -> Result<(), ParameterBoxError> {
+        self.set_min_limit_close(name, T::zero())
+    }
+
+    /// Restricts a numeric parameter to values less than zero.
+    #[track_caller]
+    pub fn set_negative<T: Numeric>(&mut self, name: &str) -> Result<(), ParameterBoxError> {
+        self.set_max_limit_open(name, T::zero())
+    }
+
+    /// Restricts a numeric parameter to values less than or equal to zero.
+    #[track_caller]
+    pub fn set_nonpositive<T: Numeric>(&mut self, name: &str) -> Result<(), ParameterBoxError> {
+        self.set_max_limit_close(name, T::zero())
+    }
+
+    /// Sets the minimum byte length of a `String` parameter.
+    #[track_caller]
+    pub fn set_min_length(&mut self, name: &str, min: usize) -> Result<(), ParameterBoxError> {
+        self.set_length_range(name, (Some(min), None))
+    }
+
+    /// Sets the maximum byte length of a `String` parameter.
+    #[track_caller]
+    pub fn set_max_length(&mut self, name: &str, max: usize) -> Result<(), ParameterBoxError> {
+        self.set_length_range(name, (None, Some(max)))
+    }
+
+    /// Removes the minimum and maximum limits without checking the current value.
+    #[track_caller]
+    pub fn clear_range(&mut self, name: &str) -> Result<(), ParameterBoxError> {
+        self.clear_min_limit(name)?;
+        self.clear_max_limit(name)
+    }
+
+    /// Removes the minimum limit without checking the current value.
+    #[track_caller]
+    pub fn clear_min_limit(&mut self, name: &str) -> Result<(), ParameterBoxError> {
         if let Some(parameter) = self.parameter_list.get_mut(name) {
-            parameter.unvisible = true;
+            parameter.parameter_core = parameter.parameter_core.without_min_limit();
+            parameter.range_string.0 = None;
             Ok(())
         } else {
             self.error_counter += 1;
@@ -394,18 +1175,13 @@ impl ParameterBox {
         }
     }
 
+    /// Removes the maximum limit without checking the current value.
     #[track_caller]
-    pub fn ref_value<T>(&mut self, name: &str) -> Result<&Option<T>, ParameterBoxError>
-    where
-        T: 'static + PartialOrd + PartialEq + Clone + Display,
-    {
-        if let Some(parameter) = self.parameter_list.get(name) {
-            Ok(&(parameter
-                .parameter_core
-                .as_ref()
-                .downcast_ref::<ParameterCore<T>>()
-                .expect("Downcast failed.")
-                .value))
+    pub fn clear_max_limit(&mut self, name: &str) -> Result<(), ParameterBoxError> {
+        if let Some(parameter) = self.parameter_list.get_mut(name) {
+            parameter.parameter_core = parameter.parameter_core.without_max_limit();
+            parameter.range_string.1 = None;
+            Ok(())
         } else {
             self.error_counter += 1;
             Err(ParameterBoxError::NotAdded(err_msg_not_added!(name)))
@@ -413,412 +1189,2285 @@ impl ParameterBox {
     }
 
     #[track_caller]
-    pub fn clone_value<T>(&mut self, name: &str) -> Result<Option<T>, ParameterBoxError>
+    pub fn set_blacklist<T>(
+        &mut self,
+        name: &str,
+        blacklist: Vec<T>,
+    ) -> Result<(), ParameterBoxError>
     where
         T: 'static + PartialOrd + PartialEq + Clone + Display,
     {
-        if let Some(parameter) = self.parameter_list.get(name) {
-            Ok(parameter
-                .parameter_core
-                .as_ref()
-                .downcast_ref::<ParameterCore<T>>()
-                .expect("Downcast failed.")
-                .value
-                .clone())
-        } else {
-            self.error_counter += 1;
-            Err(ParameterBoxError::NotAdded(err_msg_not_added!(name)))
-        }
+        self.set_list(name, ListCondition::Black(blacklist))
     }
 
     #[track_caller]
-    pub fn clone_value_forcibly<T>(&mut self, name: &str) -> T
+    pub fn set_whitelist<T>(
+        &mut self,
+        name: &str,
+        whitelist: Vec<T>,
+    ) -> Result<(), ParameterBoxError>
     where
         T: 'static + PartialOrd + PartialEq + Clone + Display,
     {
-        match unwrap_result!(self.clone_value::<T>(name)) {
-            Some(value) => value,
-            None => {
-                eprintln!("{} `{}` does not have a value.", err_msg_header!(), name);
-                std::process::exit(1);
-            }
-        }
-    }
-
-    #[track_caller]
-    pub fn ref_explanation(&mut self, name: &str) -> Result<&Option<String>, ParameterBoxError> {
-        if let Some(parameter) = self.parameter_list.get(name) {
-            Ok(&parameter.explanation)
-        } else {
-            self.error_counter += 1;
-            Err(ParameterBoxError::NotAdded(err_msg_not_added!(name)))
-        }
-    }
-
-    pub fn get_num_errors(&self) -> &u32 {
-        &self.error_counter
+        self.set_list(name, ListCondition::White(whitelist))
     }
 
+    /// Sets a blacklist or a whitelist given as a `ListCondition`.
     #[track_caller]
-    pub fn read_file(&mut self, filename: &str) -> Result<(), ParameterBoxError> {
-        let file;
-        match File::open(filename) {
-            Ok(opened_file) => file = opened_file,
-            Err(err) => return Err(err.into()),
-        }
-        let mut duplicate_checker: HashMap<String, Vec<u32>> = HashMap::new();
-        let mut line_number = 0_u32;
-        let mut error_sequence = false;
-        let mut err_msg = String::new();
-        let comment_line_header = "#";
-        for line_content in BufReader::new(file).lines() {
-            line_number += 1;
-            let line;
-            match line_content {
-                Ok(unwrapped_line) => line = unwrapped_line,
-                Err(err) => return Err(err.into()),
+    pub fn set_list<T>(
+        &mut self,
+        name: &str,
+        list: ListCondition<T>,
+    ) -> Result<(), ParameterBoxError>
+    where
+        T: 'static + PartialOrd + PartialEq + Clone + Display,
+    {
+        if let Some(parameter) = self.parameter_list.get_mut(name) {
+            if parameter.passthrough {
+                self.error_counter += 1;
+                return Err(ParameterBoxError::InvalidCondition(err_msg_passthrough!(
+                    name
+                )));
             }
-            if line.starts_with(comment_line_header) {
-                continue;
+            let mut err_msg = String::new();
+            let mut new_parameter_core = match ParameterBox::downcast_core::<T>(name, parameter) {
+                Ok(parameter_core) => Box::new(parameter_core.clone()),
+                Err(err) => {
+                    self.error_counter += 1;
+                    return Err(err);
+                }
+            };
+            parameter.list_string = ParameterBox::make_list_info_string(&list);
+            parameter.list_value_strings = ParameterBox::make_list_value_strings(&list);
+            new_parameter_core.list = Some(list);
+            if let Some(value) = &new_parameter_core.value {
+                match new_parameter_core.check_list_condition() {
+                    Err(ListError::BlacklistViolation(condition)) => {
+                        self.error_counter += 1;
+                        err_msg.push_str(err_msg_bad_condition!(name, value, condition));
+                    }
+                    Err(ListError::WhitelistViolation(condition)) => {
+                        self.error_counter += 1;
+                        err_msg.push_str(err_msg_bad_condition!(name, value, condition));
+                    }
+                    Ok(()) => (),
+                }
             }
-            if line.is_empty() {
-                continue;
+            parameter.parameter_core = new_parameter_core;
+            if err_msg.is_empty() {
+                Ok(())
+            } else {
+                Err(ParameterBoxError::InvalidCondition(err_msg))
             }
-            let name_value: Vec<&str> = line.split_whitespace().collect();
-            let name = name_value[0];
-            if !(self.parameter_list.contains_key(name)) {
+        } else {
+            self.error_counter += 1;
+            Err(ParameterBoxError::NotAdded(err_msg_not_added!(name)))
+        }
+    }
+
+    /// Removes the blacklist or whitelist without checking the current value.
+    #[track_caller]
+    pub fn clear_list(&mut self, name: &str) -> Result<(), ParameterBoxError> {
+        if let Some(parameter) = self.parameter_list.get_mut(name) {
+            parameter.parameter_core = parameter.parameter_core.without_list();
+            parameter.list_string = None;
+            parameter.list_value_strings.clear();
+            Ok(())
+        } else {
+            self.error_counter += 1;
+            Err(ParameterBoxError::NotAdded(err_msg_not_added!(name)))
+        }
+    }
+
+    /// Copies all constraints of `source` to `dest` of the same type: the range, the list, the
+    /// nonzero and finiteness checks, the length range, the regex, the validator and the warning
+    /// range.
+    #[track_caller]
+    pub fn copy_constraints_from(
+        &mut self,
+        source: &str,
+        dest: &str,
+    ) -> Result<(), ParameterBoxError> {
+        for name in [source, dest].iter() {
+            if !self.parameter_list.contains_key(*name) {
                 self.error_counter += 1;
-                ParameterBox::sequence_err_or_not(&mut error_sequence, &mut err_msg);
-                err_msg.push_str(&format!(
-                    "{} in the {}-th line of the file '{}', `{}` has not been added to a parameter box.",
-                    err_msg_header!(),
-                    line_number,
-                    filename,
-                    name
-                ));
-                continue;
-            }
-            if let Some(checker_element) = duplicate_checker.get_mut(name) {
-                checker_element.push(line_number);
-            } else {
-                duplicate_checker.insert(name.to_string(), vec![line_number]);
+                return Err(ParameterBoxError::NotAdded(err_msg_not_added!(name)));
             }
-            if name_value.len() != 2 {
+        }
+        let source_parameter = &self.parameter_list[source];
+        let dest_parameter = &self.parameter_list[dest];
+        if source_parameter.type_id != dest_parameter.type_id {
+            self.error_counter += 1;
+            return Err(ParameterBoxError::InvalidCondition(format!(
+                "{} constraints cannot be copied from `{}` ({}) to `{}` ({}) since their types are different.",
+                err_msg_header!(),
+                source,
+                source_parameter.type_string,
+                dest,
+                dest_parameter.type_string,
+            )));
+        }
+        if dest_parameter.passthrough {
+            self.error_counter += 1;
+            return Err(ParameterBoxError::InvalidCondition(err_msg_passthrough!(
+                dest
+            )));
+        }
+        let new_parameter_core = dest_parameter
+            .parameter_core
+            .with_constraints_of(source_parameter.parameter_core.as_ref());
+        let source_parameter = source_parameter.clone();
+        let dest_parameter = self.parameter_list.get_mut(dest).unwrap();
+        dest_parameter.range_string = source_parameter.range_string;
+        dest_parameter.list_string = source_parameter.list_string;
+        dest_parameter.list_value_strings = source_parameter.list_value_strings;
+        dest_parameter.constraint_strings = source_parameter.constraint_strings;
+        dest_parameter.length_range = source_parameter.length_range;
+        #[cfg(feature = "regex")]
+        {
+            dest_parameter.regex = source_parameter.regex;
+        }
+        dest_parameter.validator = source_parameter.validator;
+        dest_parameter.warning_range = source_parameter.warning_range;
+        dest_parameter.warning_range_string = source_parameter.warning_range_string;
+        let mut error_sequence = false;
+        let mut err_msg = String::new();
+        let mut conditions = new_parameter_core.violated_conditions();
+        if let Some(value) = new_parameter_core
+            .value_as_any()
+            .and_then(|value| value.downcast_ref::<String>())
+        {
+            conditions.extend(ParameterBox::violated_string_conditions(
+                dest_parameter,
+                value,
+            ));
+        }
+        for condition in conditions {
+            self.error_counter += 1;
+            ParameterBox::sequence_err_or_not(&mut error_sequence, &mut err_msg);
+            err_msg.push_str(err_msg_bad_condition!(
+                dest,
+                dest_parameter.value_string.as_ref().unwrap(),
+                condition
+            ));
+        }
+        if let (true, Some(validator), Some(value)) = (
+            err_msg.is_empty(),
+            &dest_parameter.validator,
+            new_parameter_core.value_as_any(),
+        ) {
+            if let Err(msg) = (validator.0)(value) {
                 self.error_counter += 1;
                 ParameterBox::sequence_err_or_not(&mut error_sequence, &mut err_msg);
-                err_msg.push_str(&format!( "{} in the {}-th line of the file '{}', each line must be '<name> <value>' in a parameter file.",
-                        err_msg_header!(), line_number, filename,
+                err_msg.push_str(err_msg_rejected_by_validator!(
+                    dest,
+                    dest_parameter.value_string.as_ref().unwrap(),
+                    msg
                 ));
-                continue;
             }
-            let value_string = name_value[1];
-            let mut type_error = true;
-            macro_rules! set_correct_value_by_string {
-                ($type:ty) => {
-                    if self.parameter_list[name].type_id == std::any::TypeId::of::<$type>() {
-                        type_error = false;
-                        match self.set_value_by_string::<$type>(name, value_string){
-                            Ok(()) => (),
-                            Err(ParameterBoxError::InvalidCondition(msg)) => {
-                                ParameterBox::sequence_err_or_not(&mut error_sequence, &mut err_msg);
-                                err_msg.push_str(&format!("{} (in the {}-th line of the file '{}')",msg,line_number,filename));
-                            },
-                            Err(_) => unreachable!(),
-                        }
-                    }
-                };
-                ($type_head:ty, $($type_tail:ty),+ ) =>{
-                    set_correct_value_by_string!($type_head);
-                    set_correct_value_by_string!($($type_tail),+);
-                };
+        }
+        dest_parameter.parameter_core = new_parameter_core;
+        if err_msg.is_empty() {
+            Ok(())
+        } else {
+            Err(ParameterBoxError::InvalidCondition(err_msg))
+        }
+    }
+
+    #[track_caller]
+    pub fn set_explanation(
+        &mut self,
+        name: &str,
+        explanation: String,
+    ) -> Result<(), ParameterBoxError> {
+        if let Some(parameter) = self.parameter_list.get_mut(name) {
+            parameter.explanation = Some(explanation);
+            Ok(())
+        } else {
+            self.error_counter += 1;
+            Err(ParameterBoxError::NotAdded(err_msg_not_added!(name)))
+        }
+    }
+
+    /// Assigns `name` to the section `section`, which is used by `print_section` and `print_grouped`.
+    #[track_caller]
+    pub fn set_section(&mut self, name: &str, section: &str) -> Result<(), ParameterBoxError> {
+        if let Some(parameter) = self.parameter_list.get_mut(name) {
+            parameter.section = Some(section.to_string());
+            Ok(())
+        } else {
+            self.error_counter += 1;
+            Err(ParameterBoxError::NotAdded(err_msg_not_added!(name)))
+        }
+    }
+
+    /// Returns the names of sections in the order they first appear.
+    pub fn section_names(&self) -> Vec<&str> {
+        let mut section_names = Vec::new();
+        for parameter in self.parameter_list.values() {
+            if let Some(section) = parameter.section.as_deref() {
+                if !section_names.contains(&section) {
+                    section_names.push(section);
+                }
             }
-            set_correct_value_by_string!(
-                bool, u8, u16, u32, u64, u128, i8, i16, i32, i64, i128, f32, f64, String
-            );
-            if type_error {
-                ParameterBox::sequence_err_or_not(&mut error_sequence, &mut err_msg);
-                err_msg.push_str(&format!(
-                    "{} in the {}-th line of the file '{}', the type of `{}` is {}, which cannot be read from files.",
-                    err_msg_header!(),
-                    line_number,
-                    filename,
-                    name,
-                    self.parameter_list[name].type_string
-                ));
+        }
+        section_names
+    }
+
+    #[deprecated(note = "use `set_invisible` instead")]
+    #[track_caller]
+    pub fn set_unvisible(&mut self, name: &str) -> Result<(), ParameterBoxError> {
+        self.set_invisible(name)
+    }
+
+    /// Hides a parameter from `print` and the other outputs for users.
+    #[track_caller]
+    pub fn set_invisible(&mut self, name: &str) -> Result<(), ParameterBoxError> {
+        if let Some(parameter) = self.parameter_list.get_mut(name) {
+            parameter.unvisible = true;
+            Ok(())
+        } else {
+            self.error_counter += 1;
+            Err(ParameterBoxError::NotAdded(err_msg_not_added!(name)))
+        }
+    }
+
+    /// Shows a parameter hidden by `set_invisible` again.
+    #[track_caller]
+    pub fn set_visible(&mut self, name: &str) -> Result<(), ParameterBoxError> {
+        if let Some(parameter) = self.parameter_list.get_mut(name) {
+            parameter.unvisible = false;
+            Ok(())
+        } else {
+            self.error_counter += 1;
+            Err(ParameterBoxError::NotAdded(err_msg_not_added!(name)))
+        }
+    }
+
+    /// Marks a parameter as one which must be given a value, e.g. by an input file.
+    #[track_caller]
+    pub fn mark_required(&mut self, name: &str) -> Result<(), ParameterBoxError> {
+        if let Some(parameter) = self.parameter_list.get_mut(name) {
+            parameter.required = true;
+            Ok(())
+        } else {
+            self.error_counter += 1;
+            Err(ParameterBoxError::NotAdded(err_msg_not_added!(name)))
+        }
+    }
+
+    /// Marks all parameters as required.
+    pub fn make_required_all(&mut self) {
+        for parameter in self.parameter_list.values_mut() {
+            parameter.required = true;
+        }
+    }
+
+    /// Marks all parameters as optional.
+    pub fn make_optional_all(&mut self) {
+        for parameter in self.parameter_list.values_mut() {
+            parameter.required = false;
+        }
+    }
+
+    /// Checks that every required parameter has a value.
+    ///
+    /// Parameters which are not marked as required by `mark_required` or `make_required_all` may
+    /// remain unset. Cross constraints are not checked; see `validate_all_set`.
+    #[track_caller]
+    pub fn validate_required(&self) -> Result<(), ParameterBoxError> {
+        self.validate_set_core("required parameters", |_, parameter| parameter.required)
+    }
+
+    /// Checks required parameters as `validate_required` does, and also checks cross constraints
+    /// if full validation is enabled by `set_full_validation`.
+    #[track_caller]
+    pub fn validate_all_set(&self) -> Result<(), ParameterBoxError> {
+        self.validate_required()?;
+        if self.full_validation {
+            self.validate_cross_constraints()?;
+        }
+        Ok(())
+    }
+
+    /// Sets whether `validate_all_set` also checks cross constraints.
+    pub fn set_full_validation(&mut self, enabled: bool) {
+        self.full_validation = enabled;
+    }
+
+    /// Registers a constraint on several parameters, which is checked by
+    /// `validate_cross_constraints`.
+    ///
+    /// `involved` lists the parameters mentioned in error messages. The validator receives this
+    /// box and returns the reason of a violation as `Err`.
+    #[track_caller]
+    pub fn add_cross_constraint<F>(
+        &mut self,
+        description: &str,
+        involved: &[&str],
+        validator: F,
+    ) -> Result<(), ParameterBoxError>
+    where
+        F: Fn(&ParameterBox) -> Result<(), String> + 'static,
+    {
+        for name in involved.iter() {
+            if !self.parameter_list.contains_key(*name) {
+                self.error_counter += 1;
+                return Err(ParameterBoxError::NotAdded(err_msg_not_added!(name)));
             }
         }
-        for (name, line_number_list) in duplicate_checker {
-            if line_number_list.len() != 1 {
+        self.cross_constraints.push(CrossConstraint {
+            description: description.to_string(),
+            involved: involved.iter().map(|name| name.to_string()).collect(),
+            validator: Rc::new(validator),
+        });
+        Ok(())
+    }
+
+    /// Checks all cross constraints registered by `add_cross_constraint`.
+    #[track_caller]
+    pub fn validate_cross_constraints(&self) -> Result<(), ParameterBoxError> {
+        let mut error_sequence = false;
+        let mut err_msg = String::new();
+        for cross_constraint in self.cross_constraints.iter() {
+            if let Err(msg) = (cross_constraint.validator)(self) {
                 ParameterBox::sequence_err_or_not(&mut error_sequence, &mut err_msg);
                 err_msg.push_str(&format!(
-                    "{} in the {} lines of the file '{}', `{}` is duplicate.",
+                    "{} the constraint '{}' on {} is violated: {}",
                     err_msg_header!(),
-                    line_number_list
+                    cross_constraint.description,
+                    cross_constraint
+                        .involved
                         .iter()
-                        .map(|x| format!("{}-th", x))
+                        .map(|name| format!("`{}`", name))
                         .collect::<Vec<String>>()
                         .join(", "),
-                    filename,
-                    name,
+                    msg
                 ));
             }
         }
-        if err_msg.is_empty() {
-            Ok(())
+        if err_msg.is_empty() {
+            Ok(())
+        } else {
+            Err(ParameterBoxError::InvalidCondition(err_msg))
+        }
+    }
+
+    /// Checks that every required parameter except `exceptions` has a value.
+    #[track_caller]
+    pub fn validate_set_except(&self, exceptions: &[&str]) -> Result<(), ParameterBoxError> {
+        self.validate_set_core("required parameters", |name, parameter| {
+            parameter.required && !exceptions.contains(&name)
+        })
+    }
+
+    /// Moves `name` to the first position of the parameter order.
+    #[track_caller]
+    pub fn move_to_front(&mut self, name: &str) -> Result<(), ParameterBoxError> {
+        if let Some(index) = self.parameter_list.get_index_of(name) {
+            self.parameter_list.move_index(index, 0);
+            Ok(())
+        } else {
+            self.error_counter += 1;
+            Err(ParameterBoxError::NotAdded(err_msg_not_added!(name)))
+        }
+    }
+
+    /// Moves `name` to the last position of the parameter order.
+    #[track_caller]
+    pub fn move_to_back(&mut self, name: &str) -> Result<(), ParameterBoxError> {
+        if let Some(index) = self.parameter_list.get_index_of(name) {
+            let last_index = self.parameter_list.len() - 1;
+            self.parameter_list.move_index(index, last_index);
+            Ok(())
+        } else {
+            self.error_counter += 1;
+            Err(ParameterBoxError::NotAdded(err_msg_not_added!(name)))
+        }
+    }
+
+    /// Returns a reference to the value of `name`.
+    ///
+    /// Reading a parameter which is not added is not counted as an error.
+    #[track_caller]
+    pub fn ref_value<T>(&self, name: &str) -> Result<&Option<T>, ParameterBoxError>
+    where
+        T: 'static + PartialOrd + PartialEq + Clone + Display,
+    {
+        if let Some(parameter) = self.parameter_list.get(name) {
+            ParameterBox::downcast_core::<T>(name, parameter)
+                .map(|parameter_core| &parameter_core.value)
+        } else {
+            Err(ParameterBoxError::NotAdded(err_msg_not_added!(name)))
+        }
+    }
+
+    /// Returns a copy of the value of `name`.
+    ///
+    /// Reading a parameter which is not added is not counted as an error.
+    #[track_caller]
+    pub fn clone_value<T>(&self, name: &str) -> Result<Option<T>, ParameterBoxError>
+    where
+        T: 'static + PartialOrd + PartialEq + Clone + Display,
+    {
+        self.ref_value(name).cloned()
+    }
+
+    /// Returns the value of `name`, or `NotSet` if it does not have a value.
+    #[track_caller]
+    pub fn try_value<T>(&self, name: &str) -> Result<T, ParameterBoxError>
+    where
+        T: 'static + PartialOrd + PartialEq + Clone + Display,
+    {
+        match self.ref_value::<T>(name)? {
+            Some(value) => Ok(value.clone()),
+            None => Err(ParameterBoxError::NotSet(name.to_string())),
+        }
+    }
+
+    /// Seals the parameter box, after which only read-only methods are available.
+    pub fn seal(self) -> SealedParameterBox {
+        SealedParameterBox::new(self)
+    }
+
+    #[track_caller]
+    pub fn clone_value_forcibly<T>(&mut self, name: &str) -> T
+    where
+        T: 'static + PartialOrd + PartialEq + Clone + Display,
+    {
+        match unwrap_result!(self.clone_value::<T>(name)) {
+            Some(value) => value,
+            None => {
+                eprintln!("{} `{}` does not have a value.", err_msg_header!(), name);
+                std::process::exit(1);
+            }
+        }
+    }
+
+    /// Returns the value of `name`, or `default` if it does not have a value.
+    ///
+    /// Panics if `name` is not added.
+    #[track_caller]
+    pub fn clone_value_or<T>(&mut self, name: &str, default: T) -> T
+    where
+        T: 'static + PartialOrd + PartialEq + Clone + Display,
+    {
+        panic_result!(self.clone_value::<T>(name)).unwrap_or(default)
+    }
+
+    /// Returns the values of all parameters of type `T` in the order they were added.
+    pub fn clone_all_values<T>(&self) -> Vec<(String, Option<T>)>
+    where
+        T: 'static + PartialOrd + PartialEq + Clone + Display,
+    {
+        self.parameter_list
+            .iter()
+            .filter_map(|(name, parameter)| {
+                parameter
+                    .parameter_core
+                    .as_any()
+                    .downcast_ref::<ParameterCore<T>>()
+                    .map(|parameter_core| (name.clone(), parameter_core.value.clone()))
+            })
+            .collect()
+    }
+
+    #[track_caller]
+    pub fn ref_explanation(&mut self, name: &str) -> Result<&Option<String>, ParameterBoxError> {
+        if let Some(parameter) = self.parameter_list.get(name) {
+            Ok(&parameter.explanation)
+        } else {
+            self.error_counter += 1;
+            Err(ParameterBoxError::NotAdded(err_msg_not_added!(name)))
+        }
+    }
+
+    /// Returns whether `name` has a value.
+    #[track_caller]
+    pub fn is_set(&self, name: &str) -> Result<bool, ParameterBoxError> {
+        if let Some(parameter) = self.parameter_list.get(name) {
+            Ok(parameter.value_string.is_some())
+        } else {
+            Err(ParameterBoxError::NotAdded(err_msg_not_added!(name)))
+        }
+    }
+
+    /// Returns the type name of `name`, e.g. `"f64"` or `"alloc::string::String"`.
+    #[track_caller]
+    pub fn type_name_of(&self, name: &str) -> Result<&str, ParameterBoxError> {
+        if let Some(parameter) = self.parameter_list.get(name) {
+            Ok(&parameter.type_string)
+        } else {
+            Err(ParameterBoxError::NotAdded(err_msg_not_added!(name)))
+        }
+    }
+
+    /// Returns whether `name` has the type `T`.
+    #[track_caller]
+    pub fn is_type<T: 'static>(&self, name: &str) -> Result<bool, ParameterBoxError> {
+        if let Some(parameter) = self.parameter_list.get(name) {
+            Ok(parameter.type_id == std::any::TypeId::of::<T>())
+        } else {
+            Err(ParameterBoxError::NotAdded(err_msg_not_added!(name)))
+        }
+    }
+
+    /// Returns all metadata of `name` at once.
+    #[track_caller]
+    pub fn parameter_info<'a>(
+        &'a self,
+        name: &str,
+    ) -> Result<ParameterInfo<'a>, ParameterBoxError> {
+        if let Some((name, parameter)) = self.parameter_list.get_key_value(name) {
+            Ok(ParameterInfo::new(name, parameter))
+        } else {
+            Err(ParameterBoxError::NotAdded(err_msg_not_added!(name)))
+        }
+    }
+
+    /// Returns the names of parameters for which `predicate` returns `true` in the order they
+    /// were added.
+    pub fn filter_names<F>(&self, predicate: F) -> Vec<&str>
+    where
+        F: Fn(&str, &ParameterInfo) -> bool,
+    {
+        self.parameter_list
+            .iter()
+            .filter(|(name, parameter)| predicate(name, &ParameterInfo::new(name, parameter)))
+            .map(|(name, _)| name.as_str())
+            .collect()
+    }
+
+    /// Returns the explanation of `name`, or `None` if it has no explanation or is not added.
+    pub fn get_explanation(&self, name: &str) -> Option<&str> {
+        self.parameter_list
+            .get(name)
+            .and_then(|parameter| parameter.explanation.as_deref())
+    }
+
+    /// Returns the explanation of `name`, or `""` if it has no explanation or is not added.
+    pub fn get_explanation_or_empty(&self, name: &str) -> &str {
+        self.get_explanation(name).unwrap_or("")
+    }
+
+    /// Returns the minimum and maximum limits of `name` as displayed by `print`.
+    #[track_caller]
+    pub fn get_range_display_strings(
+        &self,
+        name: &str,
+    ) -> Result<(Option<&str>, Option<&str>), ParameterBoxError> {
+        if let Some(parameter) = self.parameter_list.get(name) {
+            Ok((
+                parameter.range_string.0.as_deref(),
+                parameter.range_string.1.as_deref(),
+            ))
+        } else {
+            Err(ParameterBoxError::NotAdded(err_msg_not_added!(name)))
+        }
+    }
+
+    /// Returns the list type and the listed values of `name` as displayed by `print`.
+    #[track_caller]
+    pub fn get_list_display_string(
+        &self,
+        name: &str,
+    ) -> Result<Option<(&str, &str)>, ParameterBoxError> {
+        if let Some(parameter) = self.parameter_list.get(name) {
+            Ok(parameter
+                .list_string
+                .as_ref()
+                .map(|(label, list)| (label.as_str(), list.as_str())))
+        } else {
+            Err(ParameterBoxError::NotAdded(err_msg_not_added!(name)))
+        }
+    }
+
+    /// Returns the number of parameters.
+    pub fn len(&self) -> usize {
+        self.parameter_list.len()
+    }
+
+    /// Returns whether no parameter has been added.
+    pub fn is_empty(&self) -> bool {
+        self.parameter_list.is_empty()
+    }
+
+    /// Returns the number of parameters, which is the same as `len`.
+    pub fn total_count(&self) -> usize {
+        self.len()
+    }
+
+    /// Returns the number of parameters with values.
+    pub fn count_set(&self) -> usize {
+        self.parameter_list
+            .values()
+            .filter(|parameter| parameter.value_string.is_some())
+            .count()
+    }
+
+    /// Returns the number of parameters without values.
+    pub fn count_unset(&self) -> usize {
+        self.total_count() - self.count_set()
+    }
+
+    /// Returns whether `name` has been added.
+    pub fn contains(&self, name: &str) -> bool {
+        self.parameter_list.contains_key(name)
+    }
+
+    /// Returns whether all of `names` have been added.
+    pub fn contains_all(&self, names: &[&str]) -> bool {
+        names
+            .iter()
+            .all(|name| self.parameter_list.contains_key(*name))
+    }
+
+    /// Returns whether any of `names` has been added.
+    pub fn contains_any(&self, names: &[&str]) -> bool {
+        names
+            .iter()
+            .any(|name| self.parameter_list.contains_key(*name))
+    }
+
+    /// Returns names and views of parameters in the order they were added.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, ParameterView<'_>)> + '_ {
+        self.parameter_list
+            .iter()
+            .map(|(name, parameter)| (name.as_str(), ParameterView::new(parameter)))
+    }
+
+    /// Returns names and values of parameters with values in the order they were added.
+    pub fn iter_set_values(&self) -> impl Iterator<Item = (&str, &str)> + '_ {
+        self.parameter_list.iter().filter_map(|(name, parameter)| {
+            parameter
+                .value_string
+                .as_deref()
+                .map(|value_string| (name.as_str(), value_string))
+        })
+    }
+
+    /// Returns a map from names to values of parameters with values.
+    pub fn to_string_map(&self) -> HashMap<String, String> {
+        self.iter_set_values()
+            .map(|(name, value_string)| (name.to_string(), value_string.to_string()))
+            .collect()
+    }
+
+    /// Returns the names of parameters in the order they were added.
+    pub fn parameter_names(&self) -> impl Iterator<Item = &str> + '_ {
+        self.parameter_list.keys().map(|name| name.as_str())
+    }
+
+    /// Returns names and type names of parameters in the order they were added.
+    pub fn iter_names_and_types(&self) -> impl Iterator<Item = (&str, &str)> + '_ {
+        self.parameter_list
+            .iter()
+            .map(|(name, parameter)| (name.as_str(), parameter.type_string.as_str()))
+    }
+
+    /// Returns an approximate size of the parameter box in bytes.
+    ///
+    /// This sums the size of each parameter and its strings, so heap memory owned by values and
+    /// list constraints (e.g. the contents of a `String` value) is not included.
+    pub fn get_total_size_estimate(&self) -> usize {
+        let optional_len = |string: &Option<String>| string.as_ref().map_or(0, |x| x.len());
+        let mut size = std::mem::size_of::<ParameterBox>();
+        for (name, parameter) in self.parameter_list.iter() {
+            size += std::mem::size_of::<String>() + name.len();
+            size += std::mem::size_of::<Parameter>();
+            size += std::mem::size_of_val(parameter.parameter_core.as_ref());
+            size += parameter.type_string.len();
+            size += optional_len(&parameter.value_string);
+            size += optional_len(&parameter.range_string.0);
+            size += optional_len(&parameter.range_string.1);
+            size += parameter
+                .list_string
+                .as_ref()
+                .map_or(0, |(label, list)| label.len() + list.len());
+            size += optional_len(&parameter.explanation);
+        }
+        size
+    }
+
+    pub fn get_num_errors(&self) -> u32 {
+        self.error_counter
+    }
+
+    /// Returns `true` if any error has been counted.
+    pub fn any_errors(&self) -> bool {
+        self.error_counter > 0
+    }
+
+    /// Returns the current number of errors, e.g. to compare it after a sub-operation.
+    pub fn error_counter_snapshot(&self) -> u32 {
+        self.error_counter
+    }
+
+    /// Resets the number of errors to zero.
+    pub fn reset_error_counter(&mut self) {
+        self.error_counter = 0;
+    }
+
+    /// Returns names and values of parameters in the order they were added, with `None` for
+    /// parameters without values.
+    pub fn get_all_values_as_strings(&self) -> Vec<(String, Option<String>)> {
+        self.parameter_list
+            .iter()
+            .map(|(name, parameter)| (name.clone(), parameter.value_string.clone()))
+            .collect()
+    }
+
+    /// Sets values from a file whose lines are `<name> <value>`.
+    ///
+    /// Values containing blanks are written in single or double quotes, and double-quoted values
+    /// accept the escapes `\"`, `\\`, `\n` and `\t`.
+    /// Text from `#` outside quotes to the end of a line is a comment.
+    ///
+    /// Required parameters which still have no values after reading the file are errors.
+    #[track_caller]
+    pub fn read_file(&mut self, filename: &str) -> Result<(), ParameterBoxError> {
+        self.read_file_with_options(filename, &ParameterFileOptions::default())
+    }
+
+    /// Reads a file in the same way as `read_file` with the format given by `options`.
+    #[track_caller]
+    pub fn read_file_with_options(
+        &mut self,
+        filename: &str,
+        options: &ParameterFileOptions,
+    ) -> Result<(), ParameterBoxError> {
+        let file = File::open(filename)?;
+        self.read_core(BufReader::new(file), filename, options)
+    }
+
+    /// Reads files in order, where values in later files override those in earlier files.
+    ///
+    /// Errors in all files are returned together. A name appearing twice in one file is still an
+    /// error.
+    #[track_caller]
+    pub fn read_files(&mut self, filenames: &[&str]) -> Result<(), ParameterBoxError> {
+        let mut error_sequence = false;
+        let mut err_msg = String::new();
+        for filename in filenames.iter() {
+            let file = File::open(filename)?;
+            self.read_entries(
+                BufReader::new(file),
+                filename,
+                &ParameterFileOptions::default(),
+                &mut error_sequence,
+                &mut err_msg,
+            )?;
+        }
+        self.check_required_after_reading(
+            &format!(
+                "the files {}",
+                filenames
+                    .iter()
+                    .map(|filename| format!("'{}'", filename))
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            ),
+            &mut error_sequence,
+            &mut err_msg,
+        );
+        if err_msg.is_empty() {
+            Ok(())
+        } else {
+            Err(ParameterBoxError::InvalidInputFile(err_msg))
+        }
+    }
+
+    /// Reads a file in the same way as `read_file` if it exists, and does nothing otherwise.
+    #[track_caller]
+    pub fn read_file_if_exists(&mut self, filename: &str) -> Result<(), ParameterBoxError> {
+        match File::open(filename) {
+            Ok(file) => self.read_core(
+                BufReader::new(file),
+                filename,
+                &ParameterFileOptions::default(),
+            ),
+            Err(io_error) if io_error.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(io_error) => Err(io_error.into()),
+        }
+    }
+
+    /// Reads a file in the same way as `read_file`, but skips names which have not been added.
+    #[track_caller]
+    pub fn read_file_lenient(&mut self, filename: &str) -> Result<(), ParameterBoxError> {
+        self.read_file_with_options(
+            filename,
+            &ParameterFileOptions::default().skip_unknown_names(true),
+        )
+    }
+
+    /// Reads parameters from the standard input in the same format as `read_file`.
+    ///
+    /// Error messages refer to the input as the file '<stdin>'.
+    #[track_caller]
+    pub fn read_stdin(&mut self) -> Result<(), ParameterBoxError> {
+        self.read_stdin_core(std::io::stdin().lock())
+    }
+
+    #[track_caller]
+    fn read_stdin_core<R: BufRead>(&mut self, reader: R) -> Result<(), ParameterBoxError> {
+        self.read_core(reader, "<stdin>", &ParameterFileOptions::default())
+    }
+
+    /// Reads parameters from `content` in the same format as `read_file`.
+    ///
+    /// Error messages refer to the content as the file '<string>'.
+    #[track_caller]
+    pub fn read_from_str(&mut self, content: &str) -> Result<(), ParameterBoxError> {
+        self.read_core(
+            content.as_bytes(),
+            "<string>",
+            &ParameterFileOptions::default(),
+        )
+    }
+
+    /// Sets explanations from a TOML table or, if `filename` ends with `.json`, a JSON object,
+    /// whose keys are parameter names and whose values are explanation strings.
+    ///
+    /// TOML files need the `toml` feature and JSON files need the `serde_json` feature.
+    #[cfg(any(feature = "toml", all(feature = "serde", feature = "serde_json")))]
+    #[track_caller]
+    pub fn load_explanations_from_file(&mut self, filename: &str) -> Result<(), ParameterBoxError> {
+        let entries = if filename.ends_with(".json") {
+            ParameterBox::read_json_explanations(filename)
+        } else {
+            ParameterBox::read_toml_explanations(filename)
+        };
+        let entries = match entries {
+            Ok(entries) => entries,
+            Err(err) => {
+                self.error_counter += 1;
+                return Err(err);
+            }
+        };
+        let mut error_sequence = false;
+        let mut err_msg = String::new();
+        for (name, explanation) in entries {
+            match (self.parameter_list.get_mut(&name), explanation) {
+                (Some(parameter), Some(explanation)) => parameter.explanation = Some(explanation),
+                (Some(_), None) => {
+                    self.error_counter += 1;
+                    ParameterBox::sequence_err_or_not(&mut error_sequence, &mut err_msg);
+                    err_msg.push_str(&format!(
+                        "{} in the file '{}', the explanation of `{}` is not a string.",
+                        err_msg_header!(),
+                        filename,
+                        name
+                    ));
+                }
+                (None, _) => {
+                    self.error_counter += 1;
+                    ParameterBox::sequence_err_or_not(&mut error_sequence, &mut err_msg);
+                    err_msg.push_str(&format!(
+                        "{} in the file '{}', `{}` has not been added to a parameter box.",
+                        err_msg_header!(),
+                        filename,
+                        name
+                    ));
+                }
+            }
+        }
+        if err_msg.is_empty() {
+            Ok(())
+        } else {
+            Err(ParameterBoxError::InvalidInputFile(err_msg))
+        }
+    }
+
+    #[cfg(all(not(all(feature = "serde", feature = "serde_json")), feature = "toml"))]
+    #[track_caller]
+    fn read_json_explanations(
+        filename: &str,
+    ) -> Result<Vec<(String, Option<String>)>, ParameterBoxError> {
+        Err(ParameterBoxError::InvalidInputFile(format!(
+            "{} the file '{}' cannot be read without the `serde` and `serde_json` features.",
+            err_msg_header!(),
+            filename
+        )))
+    }
+
+    #[cfg(all(not(feature = "toml"), feature = "serde", feature = "serde_json"))]
+    #[track_caller]
+    fn read_toml_explanations(
+        filename: &str,
+    ) -> Result<Vec<(String, Option<String>)>, ParameterBoxError> {
+        Err(ParameterBoxError::InvalidInputFile(format!(
+            "{} the file '{}' cannot be read without the `toml` feature.",
+            err_msg_header!(),
+            filename
+        )))
+    }
+
+    /// Sets values from `(name, value)` pairs sorted by name without duplicates.
+    ///
+    /// The pairs are processed in one pass; unsorted or duplicated names and names which have not
+    /// been added are reported as errors.
+    #[track_caller]
+    pub fn load_from_sorted_pairs(
+        &mut self,
+        pairs: Vec<(String, String)>,
+    ) -> Result<(), ParameterBoxError> {
+        let mut error_sequence = false;
+        let mut err_msg = String::new();
+        let mut previous_name: Option<String> = None;
+        for (name, value_string) in pairs {
+            if let Some(previous_name) = &previous_name {
+                if *previous_name >= name {
+                    self.error_counter += 1;
+                    ParameterBox::sequence_err_or_not(&mut error_sequence, &mut err_msg);
+                    err_msg.push_str(&format!(
+                        "{} `{}` follows `{}`, but pairs must be sorted by name without duplicates.",
+                        err_msg_header!(),
+                        name,
+                        previous_name,
+                    ));
+                    continue;
+                }
+            }
+            if !self.parameter_list.contains_key(&name) {
+                self.error_counter += 1;
+                ParameterBox::sequence_err_or_not(&mut error_sequence, &mut err_msg);
+                err_msg.push_str(&err_msg_not_added!(name));
+            } else {
+                match self.set_value_auto_from_string(&name, &value_string) {
+                    Ok(()) => (),
+                    Err(ParameterBoxError::InvalidCondition(msg))
+                    | Err(ParameterBoxError::InvalidParse(msg)) => {
+                        ParameterBox::sequence_err_or_not(&mut error_sequence, &mut err_msg);
+                        err_msg.push_str(&msg);
+                    }
+                    Err(err) => return Err(err),
+                }
+            }
+            previous_name = Some(name);
+        }
+        if err_msg.is_empty() {
+            Ok(())
+        } else {
+            Err(ParameterBoxError::InvalidInputFile(err_msg))
+        }
+    }
+
+    /// Sets values of parameters which have values in `other`, skipping parameters not added to
+    /// `self`.
+    #[track_caller]
+    pub fn merge_from(&mut self, other: &ParameterBox) -> Result<(), ParameterBoxError> {
+        let mut error_sequence = false;
+        let mut err_msg = String::new();
+        for (name, value_string) in other.iter_set_values() {
+            if !self.parameter_list.contains_key(name) {
+                continue;
+            }
+            match self.set_value_auto_from_string(name, value_string) {
+                Ok(()) => (),
+                Err(ParameterBoxError::InvalidCondition(msg))
+                | Err(ParameterBoxError::InvalidParse(msg)) => {
+                    ParameterBox::sequence_err_or_not(&mut error_sequence, &mut err_msg);
+                    err_msg.push_str(&msg);
+                }
+                Err(_) => unreachable!(),
+            }
+        }
+        if err_msg.is_empty() {
+            Ok(())
+        } else {
+            Err(ParameterBoxError::InvalidCondition(err_msg))
+        }
+    }
+
+    /// Sets values from environment variables named `{PREFIX}_{NAME}`, where `NAME` is the
+    /// uppercased parameter name with characters other than `[A-Za-z0-9_]` replaced with `_`.
+    ///
+    /// Parameters without the environment variables are skipped.
+    #[track_caller]
+    pub fn read_env_vars_with_prefix(&mut self, prefix: &str) -> Result<(), ParameterBoxError> {
+        for name in self
+            .parameter_list
+            .keys()
+            .cloned()
+            .collect::<Vec<String>>()
+            .iter()
+        {
+            let env_var_name = ParameterBox::env_var_name(prefix, name);
+            match std::env::var(&env_var_name) {
+                Ok(value_string) => self.set_value_auto_from_string(name, &value_string)?,
+                Err(std::env::VarError::NotPresent) => (),
+                Err(std::env::VarError::NotUnicode(_)) => {
+                    self.error_counter += 1;
+                    return Err(ParameterBoxError::InvalidParse(format!(
+                        "{} the environment variable `{}` for `{}` is not valid unicode.",
+                        err_msg_header!(),
+                        env_var_name,
+                        name
+                    )));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns `export {PREFIX}_{NAME}=value` lines of parameters with values, which can be read by
+    /// `read_env_vars_with_prefix`.
+    pub fn to_shell_exports(&self, prefix: &str) -> String {
+        let mut exports = String::new();
+        for (name, value_string) in self.iter_set_values() {
+            exports.push_str(&format!(
+                "export {}={}\n",
+                ParameterBox::env_var_name(prefix, name),
+                ParameterBox::quote_shell(value_string)
+            ));
+        }
+        exports
+    }
+
+    /// Sets values from command-line arguments of the form `--name value` or `--name=value`.
+    ///
+    /// `bool` parameters also accept `--name` for `true` and `--no-name` for `false`; `--name`
+    /// takes the next argument as its value only if it parses as a `bool`. `--help` prints
+    /// parameters to the standard output and returns `HelpRequested`. Unknown names and stray
+    /// arguments are errors unless `set_lenient_cli_args(true)` has been called, in which case
+    /// they are skipped. The program name must not be included in `args`.
+    #[track_caller]
+    pub fn read_cli_args(&mut self, args: &[String]) -> Result<(), ParameterBoxError> {
+        let mut args = args.iter().peekable();
+        while let Some(arg) = args.next() {
+            if arg == "--help" {
+                self.print(&mut std::io::stdout())?;
+                return Err(ParameterBoxError::HelpRequested(
+                    "help was requested.".to_string(),
+                ));
+            }
+            let name_value = match arg.strip_prefix("--") {
+                Some(name_value) => name_value,
+                None => {
+                    if self.lenient_cli_args {
+                        continue;
+                    }
+                    self.error_counter += 1;
+                    return Err(ParameterBoxError::InvalidParse(format!(
+                        "{} the argument '{}' is not of the form `--name value` or `--name=value`.",
+                        err_msg_header!(),
+                        arg
+                    )));
+                }
+            };
+            let (name, value_string) = match name_value.split_once('=') {
+                Some((name, value_string)) => (name, Some(value_string)),
+                None => (name_value, None),
+            };
+            let is_bool = |name: &str| {
+                self.parameter_list
+                    .get(name)
+                    .is_some_and(|parameter| parameter.type_id == std::any::TypeId::of::<bool>())
+            };
+            if value_string.is_none() && is_bool(name) {
+                let value = match args.peek().and_then(|next| ParameterBox::parse_bool(next)) {
+                    Some(value) => {
+                        args.next();
+                        value
+                    }
+                    None => true,
+                };
+                self.set_value(name, value)?;
+                continue;
+            }
+            if let (None, Some(negated_name)) = (value_string, name.strip_prefix("no-")) {
+                if !self.parameter_list.contains_key(name) && is_bool(negated_name) {
+                    self.set_value(negated_name, false)?;
+                    continue;
+                }
+            }
+            if !self.parameter_list.contains_key(name) {
+                if self.lenient_cli_args {
+                    continue;
+                }
+                self.error_counter += 1;
+                return Err(ParameterBoxError::NotAdded(err_msg_not_added!(name)));
+            }
+            let value_string = match value_string {
+                Some(value_string) => value_string,
+                None => match args.next() {
+                    Some(value_string) => value_string,
+                    None => {
+                        self.error_counter += 1;
+                        return Err(ParameterBoxError::InvalidParse(format!(
+                            "{} the argument '{}' is not followed by a value.",
+                            err_msg_header!(),
+                            arg
+                        )));
+                    }
+                },
+            };
+            self.set_value_auto_from_string(name, value_string)?;
+        }
+        Ok(())
+    }
+
+    /// Sets whether `read_cli_args` skips unknown names and stray arguments.
+    pub fn set_lenient_cli_args(&mut self, lenient: bool) {
+        self.lenient_cli_args = lenient;
+    }
+
+    /// Writes parameters with values to a file in the format read by `read_file`.
+    ///
+    /// Explanations are written as comment lines above the values. Parameters without values
+    /// are skipped, while invisible parameters are written.
+    #[track_caller]
+    pub fn write_file(&self, filename: &str) -> Result<(), ParameterBoxError> {
+        let mut file = File::create(filename)?;
+        match self.write_file_core(&mut file) {
+            Ok(()) => Ok(()),
+            Err(io_error) => Err(io_error.into()),
+        }
+    }
+
+    #[track_caller]
+    pub fn print<T: Write>(&self, writer: &mut T) -> Result<(), ParameterBoxError> {
+        match self.print_core(writer, |parameter| !parameter.unvisible) {
+            Ok(()) => Ok(()),
+            Err(io_error) => Err(io_error.into()),
+        }
+    }
+
+    /// Prints visible parameters in the section `section`.
+    #[track_caller]
+    pub fn print_section<T: Write>(
+        &self,
+        section: &str,
+        writer: &mut T,
+    ) -> Result<(), ParameterBoxError> {
+        match self.print_core(writer, |parameter| {
+            !parameter.unvisible && parameter.section.as_deref() == Some(section)
+        }) {
+            Ok(()) => Ok(()),
+            Err(io_error) => Err(io_error.into()),
+        }
+    }
+
+    /// Prints visible parameters grouped by section with section headers.
+    ///
+    /// Parameters without a section are printed last under the `Ungrouped` header.
+    #[track_caller]
+    pub fn print_grouped<T: Write>(&self, writer: &mut T) -> Result<(), ParameterBoxError> {
+        match self.print_grouped_core(writer) {
+            Ok(()) => Ok(()),
+            Err(io_error) => Err(io_error.into()),
+        }
+    }
+
+    /// Returns the output of [`print`](ParameterBox::print) as a `String`.
+    #[track_caller]
+    pub fn print_to_string(&self) -> Result<String, ParameterBoxError> {
+        let mut buffer = Vec::new();
+        self.print(&mut buffer)?;
+        Ok(String::from_utf8(buffer).expect("Printed output is not valid UTF-8."))
+    }
+
+    /// Prints visible parameters that have values, skipping unset ones.
+    #[track_caller]
+    pub fn print_set_only<T: Write>(&self, writer: &mut T) -> Result<(), ParameterBoxError> {
+        match self.print_core(writer, |parameter| {
+            !parameter.unvisible && parameter.value_string.is_some()
+        }) {
+            Ok(()) => Ok(()),
+            Err(io_error) => Err(io_error.into()),
+        }
+    }
+
+    /// Prints visible parameters like [`print`](ParameterBox::print), sorted alphabetically by name.
+    #[track_caller]
+    pub fn print_sorted<T: Write>(&self, writer: &mut T) -> Result<(), ParameterBoxError> {
+        let mut sorted_names = self.parameter_list.keys().collect::<Vec<&String>>();
+        sorted_names.sort();
+        match self.print_names_core(writer, sorted_names.into_iter(), |parameter| {
+            !parameter.unvisible
+        }) {
+            Ok(()) => Ok(()),
+            Err(io_error) => Err(io_error.into()),
+        }
+    }
+
+    /// Prints visible parameters as a table, one row per parameter.
+    ///
+    /// Column widths are computed from the longest entry of each column.
+    #[track_caller]
+    pub fn print_table<T: Write>(&self, writer: &mut T) -> Result<(), ParameterBoxError> {
+        match self.print_table_core(writer) {
+            Ok(()) => Ok(()),
+            Err(io_error) => Err(io_error.into()),
+        }
+    }
+
+    /// Prints only invisible parameters in the same format as `print`.
+    #[track_caller]
+    pub fn print_only_invisible<T: Write>(&self, writer: &mut T) -> Result<(), ParameterBoxError> {
+        match self.print_core(writer, |parameter| parameter.unvisible) {
+            Ok(()) => Ok(()),
+            Err(io_error) => Err(io_error.into()),
+        }
+    }
+
+    /// Prints visible parameters whose values differ from their default values.
+    ///
+    /// Parameters without default values are printed if they have values.
+    #[track_caller]
+    pub fn print_nondefault<T: Write>(&self, writer: &mut T) -> Result<(), ParameterBoxError> {
+        match self.print_core(writer, |parameter| {
+            !parameter.unvisible
+                && parameter.value_string.is_some()
+                && parameter.value_string != parameter.default_string
+        }) {
+            Ok(()) => Ok(()),
+            Err(io_error) => Err(io_error.into()),
+        }
+    }
+
+    /// Prints `name = value`, or `name = (unset)` if `name` does not have a value.
+    #[track_caller]
+    pub fn print_value_only<T: Write>(
+        &self,
+        writer: &mut T,
+        name: &str,
+    ) -> Result<(), ParameterBoxError> {
+        if let Some(parameter) = self.parameter_list.get(name) {
+            let value_string = parameter.value_string.as_deref().unwrap_or("(unset)");
+            match writeln!(writer, "{} = {}", name, value_string) {
+                Ok(()) => Ok(()),
+                Err(io_error) => Err(io_error.into()),
+            }
+        } else {
+            Err(ParameterBoxError::NotAdded(err_msg_not_added!(name)))
+        }
+    }
+
+    /// Prints a warning line for each parameter without a value.
+    #[track_caller]
+    pub fn print_unset_warnings<T: Write>(&self, writer: &mut T) -> Result<(), ParameterBoxError> {
+        match self.print_unset_warnings_core(writer) {
+            Ok(()) => Ok(()),
+            Err(io_error) => Err(io_error.into()),
+        }
+    }
+
+    /// Prints visible parameters as an XML document.
+    #[track_caller]
+    pub fn print_xml<T: Write>(&self, writer: &mut T) -> Result<(), ParameterBoxError> {
+        match self.print_xml_core(writer) {
+            Ok(()) => Ok(()),
+            Err(io_error) => Err(io_error.into()),
+        }
+    }
+
+    /// Prints parameters as a Markdown table.
+    ///
+    /// Invisible parameters are printed with `(hidden)` if `show_invisible` is `true`.
+    #[track_caller]
+    pub fn print_markdown<T: Write>(
+        &self,
+        writer: &mut T,
+        show_invisible: bool,
+    ) -> Result<(), ParameterBoxError> {
+        match self.print_markdown_core(writer, show_invisible) {
+            Ok(()) => Ok(()),
+            Err(io_error) => Err(io_error.into()),
+        }
+    }
+
+    /// Prints all parameters, including invisible ones, as a JSON object keyed by name.
+    #[track_caller]
+    pub fn print_json<T: Write>(&self, writer: &mut T) -> Result<(), ParameterBoxError> {
+        match self.print_json_core(writer) {
+            Ok(()) => Ok(()),
+            Err(io_error) => Err(io_error.into()),
+        }
+    }
+
+    #[track_caller]
+    fn read_core<R: BufRead>(
+        &mut self,
+        reader: R,
+        filename: &str,
+        options: &ParameterFileOptions,
+    ) -> Result<(), ParameterBoxError> {
+        let mut error_sequence = false;
+        let mut err_msg = String::new();
+        self.read_entries(reader, filename, options, &mut error_sequence, &mut err_msg)?;
+        self.check_required_after_reading(
+            &format!("the file '{}'", filename),
+            &mut error_sequence,
+            &mut err_msg,
+        );
+        if err_msg.is_empty() {
+            Ok(())
+        } else {
+            Err(ParameterBoxError::InvalidInputFile(err_msg))
+        }
+    }
+
+    /// Sets values from the lines of `reader` and appends errors to `err_msg`.
+    ///
+    /// Only I/O errors are returned as `Err`.
+    #[track_caller]
+    fn read_entries<R: BufRead>(
+        &mut self,
+        reader: R,
+        filename: &str,
+        options: &ParameterFileOptions,
+        error_sequence: &mut bool,
+        err_msg: &mut String,
+    ) -> Result<(), ParameterBoxError> {
+        let mut duplicate_checker: HashMap<String, Vec<u32>> = HashMap::new();
+        let mut line_number = 0_u32;
+        let comment_line_header = "#";
+        for line_content in reader.lines() {
+            line_number += 1;
+            let line = line_content?;
+            // Trim a trailing '\r' of CRLF files as well as surrounding blanks.
+            let line = line.trim();
+            if line.starts_with(comment_line_header) {
+                continue;
+            }
+            if line.is_empty() {
+                continue;
+            }
+            let (name, value_part) = options.separator.split_line(line);
+            let value_part = ParameterBox::strip_inline_comment(value_part);
+            if !(self.parameter_list.contains_key(name)) {
+                if options.skip_unknown_names {
+                    continue;
+                }
+                self.error_counter += 1;
+                ParameterBox::sequence_err_or_not(error_sequence, err_msg);
+                err_msg.push_str(&format!(
+                    "{} in the {}-th line of the file '{}', `{}` has not been added to a parameter box.",
+                    err_msg_header!(),
+                    line_number,
+                    filename,
+                    name
+                ));
+                continue;
+            }
+            if let Some(checker_element) = duplicate_checker.get_mut(name) {
+                checker_element.push(line_number);
+            } else {
+                duplicate_checker.insert(name.to_string(), vec![line_number]);
+            }
+            let value_string = if value_part.starts_with('"') || value_part.starts_with('\'') {
+                ParameterBox::unquote(value_part)
+            } else if value_part.is_empty() || value_part.contains(char::is_whitespace) {
+                None
+            } else {
+                Some(value_part.to_string())
+            };
+            let value_string = match value_string {
+                Some(value_string) => value_string,
+                None => {
+                    self.error_counter += 1;
+                    ParameterBox::sequence_err_or_not(error_sequence, err_msg);
+                    err_msg.push_str(&format!( "{} in the {}-th line of the file '{}', each line must be {} in a parameter file, where values with blanks must be quoted.",
+                            err_msg_header!(), line_number, filename, options.separator.line_format(),
+                    ));
+                    continue;
+                }
+            };
+            match self.set_value_auto_from_string(name, &value_string) {
+                Ok(()) => (),
+                Err(ParameterBoxError::InvalidCondition(msg))
+                | Err(ParameterBoxError::InvalidParse(msg)) => {
+                    ParameterBox::sequence_err_or_not(error_sequence, err_msg);
+                    err_msg.push_str(&format!(
+                        "{} (in the {}-th line of the file '{}')",
+                        msg, line_number, filename
+                    ));
+                }
+                Err(_) => unreachable!(),
+            }
+        }
+        for (name, line_number_list) in duplicate_checker {
+            if line_number_list.len() != 1 {
+                ParameterBox::sequence_err_or_not(error_sequence, err_msg);
+                err_msg.push_str(&format!(
+                    "{} in the {} lines of the file '{}', `{}` is duplicate.",
+                    err_msg_header!(),
+                    line_number_list
+                        .iter()
+                        .map(|x| format!("{}-th", x))
+                        .collect::<Vec<String>>()
+                        .join(", "),
+                    filename,
+                    name,
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Appends an error to `err_msg` if required parameters have no values after reading `source`.
+    #[track_caller]
+    fn check_required_after_reading(
+        &self,
+        source: &str,
+        error_sequence: &mut bool,
+        err_msg: &mut String,
+    ) {
+        if let Err(ParameterBoxError::InvalidInputFile(msg)) = self.validate_set_except(&[]) {
+            ParameterBox::sequence_err_or_not(error_sequence, err_msg);
+            err_msg.push_str(&format!("{} (after reading {})", msg, source));
+        }
+    }
+
+    #[track_caller]
+    fn set_value_by_string<T>(
+        &mut self,
+        name: &str,
+        value_string: &str,
+    ) -> Result<(), ParameterBoxError>
+    where
+        T: 'static + PartialOrd + PartialEq + Clone + Display + FromStr,
+    {
+        match T::from_str(value_string) {
+            Ok(value) => self.set_value(name, value),
+            Err(_) => {
+                self.error_counter += 1;
+                Err(ParameterBoxError::InvalidParse(format!(
+                    "{} `{}` = {} cannot be parsed as {}.",
+                    err_msg_header!(),
+                    name,
+                    value_string,
+                    std::any::type_name::<T>(),
+                )))
+            }
+        }
+    }
+
+    /// Sets a value parsed from `value_string` as the type with which `name` was added.
+    ///
+    /// The types which can be read from files are supported, and `bool` parameters also accept
+    /// `yes`, `no`, `1` and `0`.
+    #[track_caller]
+    pub fn set_value_auto_from_string(
+        &mut self,
+        name: &str,
+        value_string: &str,
+    ) -> Result<(), ParameterBoxError> {
+        let type_id = if let Some(parameter) = self.parameter_list.get(name) {
+            parameter.type_id
+        } else {
+            self.error_counter += 1;
+            return Err(ParameterBoxError::NotAdded(err_msg_not_added!(name)));
+        };
+        if type_id == std::any::TypeId::of::<bool>() {
+            if let Some(value) = ParameterBox::parse_bool(value_string) {
+                return self.set_value(name, value);
+            }
+        }
+        macro_rules! set_correct_value_by_string {
+            ($type:ty) => {
+                if type_id == std::any::TypeId::of::<$type>() {
+                    return self.set_value_by_string::<$type>(name, value_string);
+                }
+            };
+            ($type_head:ty, $($type_tail:ty),+ ) =>{
+                set_correct_value_by_string!($type_head);
+                set_correct_value_by_string!($($type_tail),+);
+            };
+        }
+        with_readable_types!(set_correct_value_by_string);
+        self.error_counter += 1;
+        Err(ParameterBoxError::InvalidParse(format!(
+            "{} the type of `{}` is {}, which cannot be read from strings. Set its value with `set_value` instead.",
+            err_msg_header!(),
+            name,
+            self.parameter_list[name].type_string
+        )))
+    }
+
+    /// Parses `true`, `false`, `yes`, `no`, `1` and `0` case-insensitively.
+    fn parse_bool(value_string: &str) -> Option<bool> {
+        match value_string.to_ascii_lowercase().as_str() {
+            "true" | "yes" | "1" => Some(true),
+            "false" | "no" | "0" => Some(false),
+            _ => None,
+        }
+    }
+
+    #[track_caller]
+    fn set_range<T>(
+        &mut self,
+        name: &str,
+        range: (RangeCondition<T>, RangeCondition<T>),
+    ) -> Result<(), ParameterBoxError>
+    where
+        T: 'static + PartialOrd + PartialEq + Clone + Display,
+    {
+        if let Some(parameter) = self.parameter_list.get_mut(name) {
+            if parameter.passthrough {
+                self.error_counter += 1;
+                return Err(ParameterBoxError::InvalidCondition(err_msg_passthrough!(
+                    name
+                )));
+            }
+            let mut error_sequence = false;
+            let mut err_msg = String::new();
+            let mut new_parameter_core = match ParameterBox::downcast_core::<T>(name, parameter) {
+                Ok(parameter_core) => Box::new(parameter_core.clone()),
+                Err(err) => {
+                    self.error_counter += 1;
+                    return Err(err);
+                }
+            };
+            parameter.range_string.0 = ParameterBox::make_min_limit_string(&range.0);
+            parameter.range_string.1 = ParameterBox::make_max_limit_string(&range.1);
+            new_parameter_core.range = (Some(range.0), Some(range.1));
+            if let Some(value) = &new_parameter_core.value {
+                if let Err(RangeError::LessThanMinLimit(condition)) =
+                    new_parameter_core.check_min_limit()
+                {
+                    self.error_counter += 1;
+                    ParameterBox::sequence_err_or_not(&mut error_sequence, &mut err_msg);
+                    err_msg.push_str(err_msg_bad_condition!(name, value, condition));
+                }
+                if let Err(RangeError::LargerThanMaxLimit(condition)) =
+                    new_parameter_core.check_max_limit()
+                {
+                    self.error_counter += 1;
+                    ParameterBox::sequence_err_or_not(&mut error_sequence, &mut err_msg);
+                    err_msg.push_str(err_msg_bad_condition!(name, value, condition));
+                }
+            }
+            parameter.parameter_core = new_parameter_core;
+            if err_msg.is_empty() {
+                Ok(())
+            } else {
+                Err(ParameterBoxError::InvalidCondition(err_msg))
+            }
+        } else {
+            self.error_counter += 1;
+            Err(ParameterBoxError::NotAdded(err_msg_not_added!(name)))
+        }
+    }
+
+    #[track_caller]
+    fn set_warning_range<T>(
+        &mut self,
+        name: &str,
+        range: (RangeCondition<T>, RangeCondition<T>),
+    ) -> Result<(), ParameterBoxError>
+    where
+        T: 'static + PartialOrd + PartialEq + Clone + Display,
+    {
+        if let Some(parameter) = self.parameter_list.get_mut(name) {
+            if parameter.passthrough {
+                self.error_counter += 1;
+                return Err(ParameterBoxError::InvalidCondition(err_msg_passthrough!(
+                    name
+                )));
+            }
+            let value = match ParameterBox::downcast_core::<T>(name, parameter) {
+                Ok(parameter_core) => parameter_core.value.clone(),
+                Err(err) => {
+                    self.error_counter += 1;
+                    return Err(err);
+                }
+            };
+            parameter.warning_range_string = (
+                ParameterBox::make_min_limit_string(&range.0),
+                ParameterBox::make_max_limit_string(&range.1),
+            );
+            let warning_range = Validator(Rc::new(move |value: &dyn Any| {
+                let mut parameter_core = ParameterCore::<T>::new();
+                parameter_core.value = value.downcast_ref::<T>().cloned();
+                parameter_core.range = (Some(range.0.clone()), Some(range.1.clone()));
+                match (
+                    parameter_core.check_min_limit(),
+                    parameter_core.check_max_limit(),
+                ) {
+                    (Err(RangeError::LessThanMinLimit(condition)), _)
+                    | (_, Err(RangeError::LargerThanMaxLimit(condition))) => Err(condition),
+                    _ => Ok(()),
+                }
+            }));
+            if let Some(value) = &value {
+                if let Err(condition) = (warning_range.0)(value) {
+                    self.warnings
+                        .push(ParameterBox::warning_msg(name, value, &condition));
+                }
+            }
+            parameter.warning_range = Some(warning_range);
+            Ok(())
+        } else {
+            self.error_counter += 1;
+            Err(ParameterBoxError::NotAdded(err_msg_not_added!(name)))
+        }
+    }
+
+    fn warning_msg<T: Display>(name: &str, value: &T, condition: &str) -> String {
+        format!(
+            "`{0}` = {1} does not satisfy the advisory condition that `{0}` {2}.",
+            name, value, condition
+        )
+    }
+
+    #[track_caller]
+    fn validate_set_core<F>(&self, description: &str, filter: F) -> Result<(), ParameterBoxError>
+    where
+        F: Fn(&str, &Parameter) -> bool,
+    {
+        let unset_names: Vec<String> = self
+            .parameter_list
+            .iter()
+            .filter(|(name, parameter)| filter(name, parameter) && parameter.value_string.is_none())
+            .map(|(name, _)| format!("`{}`", name))
+            .collect();
+        if unset_names.is_empty() {
+            Ok(())
+        } else {
+            Err(ParameterBoxError::InvalidInputFile(format!(
+                "{} {} {} do not have values.",
+                err_msg_header!(),
+                description,
+                unset_names.join(", ")
+            )))
+        }
+    }
+
+    fn write_file_core<T: Write>(&self, writer: &mut T) -> std::io::Result<()> {
+        let mut writer = BufWriter::new(writer);
+        for (name, parameter) in self.parameter_list.iter() {
+            if let Some(value_string) = &parameter.value_string {
+                if let Some(explanation) = &parameter.explanation {
+                    for explanation_line in explanation.lines() {
+                        writeln!(writer, "# {}", explanation_line)?;
+                    }
+                }
+                writeln!(
+                    writer,
+                    "{} {}",
+                    name,
+                    ParameterBox::quote_value(value_string)
+                )?;
+            }
+        }
+        writer.flush()
+    }
+
+    /// Double-quotes `value_string` if it cannot be read back as it is.
+    fn quote_value(value_string: &str) -> String {
+        if value_string.is_empty()
+            || value_string.contains(char::is_whitespace)
+            || value_string.contains('#')
+            || value_string.starts_with('"')
+            || value_string.starts_with('\'')
+        {
+            let mut quoted = String::from('"');
+            for c in value_string.chars() {
+                match c {
+                    '"' => quoted.push_str("\\\""),
+                    '\\' => quoted.push_str("\\\\"),
+                    '\n' => quoted.push_str("\\n"),
+                    '\t' => quoted.push_str("\\t"),
+                    c => quoted.push(c),
+                }
+            }
+            quoted.push('"');
+            quoted
+        } else {
+            value_string.to_string()
+        }
+    }
+
+    fn print_core<T, F>(&self, writer: &mut T, filter: F) -> std::io::Result<()>
+    where
+        T: Write,
+        F: Fn(&Parameter) -> bool,
+    {
+        self.print_names_core(writer, self.parameter_list.keys(), filter)
+    }
+
+    fn print_names_core<'a, T, I, F>(
+        &self,
+        writer: &mut T,
+        names: I,
+        filter: F,
+    ) -> std::io::Result<()>
+    where
+        T: Write,
+        I: Iterator<Item = &'a String>,
+        F: Fn(&Parameter) -> bool,
+    {
+        let mut writer = BufWriter::new(writer);
+        for name in names {
+            let parameter = self.parameter_list.get(name).unwrap();
+            if !filter(parameter) {
+                continue;
+            }
+            // Name
+            if parameter.required {
+                writeln!(writer, "{} [required]\n----------------------------", name)?;
+            } else {
+                writeln!(writer, "{}\n----------------------------", name)?;
+            }
+            // Type
+            writeln!(writer, "{:14}| {}", "Type", &parameter.type_string)?;
+            // Value
+            if let Some(value_string) = &parameter.value_string {
+                writeln!(writer, "{:14}| {}", "Default value", value_string)?;
+            }
+            // Range, list and other constraints
+            for (label, condition) in ParameterBox::condition_rows(name, parameter) {
+                writeln!(writer, "{:14}| {}", label, condition)?;
+            }
+            // Explanation
+            if let Some(explanation) = &parameter.explanation {
+                writeln!(writer, "{:14}| {}", "Explanation", explanation)?;
+            }
+            writeln!(writer)?;
+        }
+        writer.flush()
+    }
+
+    fn print_grouped_core<T: Write>(&self, writer: &mut T) -> std::io::Result<()> {
+        let mut sections = self
+            .section_names()
+            .into_iter()
+            .map(Some)
+            .collect::<Vec<Option<&str>>>();
+        sections.push(None);
+        for section in sections {
+            if !self
+                .parameter_list
+                .values()
+                .any(|parameter| !parameter.unvisible && parameter.section.as_deref() == section)
+            {
+                continue;
+            }
+            writeln!(writer, "[{}]\n", section.unwrap_or("Ungrouped"))?;
+            self.print_core(writer, |parameter| {
+                !parameter.unvisible && parameter.section.as_deref() == section
+            })?;
+        }
+        Ok(())
+    }
+
+    fn print_table_core<T: Write>(&self, writer: &mut T) -> std::io::Result<()> {
+        let visible_parameters = self
+            .parameter_list
+            .iter()
+            .filter(|(_, parameter)| !parameter.unvisible)
+            .collect::<Vec<(&String, &Parameter)>>();
+        let mut rows = vec![[
+            "Name".to_string(),
+            "Type".to_string(),
+            "Value".to_string(),
+            "Constraint".to_string(),
+            "Explanation".to_string(),
+        ]];
+        for (name, parameter) in visible_parameters {
+            let name_cell = if parameter.required {
+                format!("{} [required]", name)
+            } else {
+                name.clone()
+            };
+            let condition_cell = ParameterBox::condition_rows(name, parameter)
+                .iter()
+                .map(|(label, condition)| format!("{}: {}", label, condition))
+                .collect::<Vec<String>>()
+                .join("; ");
+            rows.push([
+                name_cell,
+                parameter.type_string.clone(),
+                parameter.value_string.clone().unwrap_or_default(),
+                condition_cell,
+                parameter.explanation.clone().unwrap_or_default(),
+            ]);
+        }
+        // First pass: compute column widths.
+        let mut widths = [0; 4];
+        for row in rows.iter() {
+            for (width, cell) in widths.iter_mut().zip(row.iter()) {
+                *width = (*width).max(cell.chars().count());
+            }
+        }
+        // Second pass: print aligned rows.
+        let mut writer = BufWriter::new(writer);
+        for (row_index, row) in rows.iter().enumerate() {
+            let line = format!(
+                "{:w0$} | {:w1$} | {:w2$} | {:w3$} | {}",
+                row[0],
+                row[1],
+                row[2],
+                row[3],
+                row[4],
+                w0 = widths[0],
+                w1 = widths[1],
+                w2 = widths[2],
+                w3 = widths[3]
+            );
+            writeln!(writer, "{}", line.trim_end())?;
+            if row_index == 0 {
+                writeln!(
+                    writer,
+                    "{}",
+                    widths
+                        .iter()
+                        .map(|width| "-".repeat(*width))
+                        .chain(std::iter::once("-".repeat("Explanation".len())))
+                        .collect::<Vec<String>>()
+                        .join("-+-")
+                )?;
+            }
+        }
+        writer.flush()
+    }
+
+    fn print_unset_warnings_core<T: Write>(&self, writer: &mut T) -> std::io::Result<()> {
+        let mut writer = BufWriter::new(writer);
+        for (name, parameter) in self.parameter_list.iter() {
+            if parameter.value_string.is_none() {
+                writeln!(
+                    writer,
+                    "WARNING: parameter `{}` ({}) has no value set",
+                    name, parameter.type_string
+                )?;
+            }
+        }
+        writer.flush()
+    }
+
+    fn print_xml_core<T: Write>(&self, writer: &mut T) -> std::io::Result<()> {
+        let mut writer = BufWriter::new(writer);
+        writeln!(writer, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+        writeln!(writer, "<parameters>")?;
+        for (name, parameter) in self.parameter_list.iter() {
+            if parameter.unvisible {
+                continue;
+            }
+            writeln!(
+                writer,
+                r#"  <parameter name="{}" type="{}">"#,
+                ParameterBox::escape_xml(name),
+                ParameterBox::escape_xml(&parameter.type_string)
+            )?;
+            // Value
+            if let Some(value_string) = &parameter.value_string {
+                writeln!(
+                    writer,
+                    "    <value>{}</value>",
+                    ParameterBox::escape_xml(value_string)
+                )?;
+            }
+            // Range
+            if parameter.range_string != (None, None) {
+                writeln!(writer, "    <range>")?;
+                if let Some(min_limit_string) = &parameter.range_string.0 {
+                    writeln!(
+                        writer,
+                        "      <min>{}</min>",
+                        ParameterBox::escape_xml(min_limit_string)
+                    )?;
+                }
+                if let Some(max_limit_string) = &parameter.range_string.1 {
+                    writeln!(
+                        writer,
+                        "      <max>{}</max>",
+                        ParameterBox::escape_xml(max_limit_string)
+                    )?;
+                }
+                writeln!(writer, "    </range>")?;
+            }
+            // List
+            if let Some(list_string) = &parameter.list_string {
+                let tag = list_string.0.to_lowercase();
+                writeln!(
+                    writer,
+                    "    <{0}>{1}</{0}>",
+                    tag,
+                    ParameterBox::escape_xml(&list_string.1)
+                )?;
+            }
+            // Explanation
+            if let Some(explanation) = &parameter.explanation {
+                writeln!(
+                    writer,
+                    "    <explanation>{}</explanation>",
+                    ParameterBox::escape_xml(explanation)
+                )?;
+            }
+            writeln!(writer, "  </parameter>")?;
+        }
+        writeln!(writer, "</parameters>")?;
+        writer.flush()
+    }
+
+    /// Returns labels and display strings of the constraints of a parameter.
+    fn condition_rows(name: &str, parameter: &Parameter) -> Vec<(String, String)> {
+        let mut rows = Vec::new();
+        // Range
+        match &parameter.range_string {
+            (Some(min_limit_string), Some(max_limit_string)) => rows.push((
+                "Range".to_string(),
+                format!("{} {} {}", min_limit_string, name, max_limit_string),
+            )),
+            (Some(min_limit_string), None) => rows.push((
+                "Range".to_string(),
+                format!("{} {}", min_limit_string, name),
+            )),
+            (None, Some(max_limit_string)) => rows.push((
+                "Range".to_string(),
+                format!("{} {}", name, max_limit_string),
+            )),
+            (None, None) => (),
+        }
+        // Warning range
+        if let (Some(min_limit_string), Some(max_limit_string)) = &parameter.warning_range_string {
+            rows.push((
+                "Warning range".to_string(),
+                format!("{} {} {}", min_limit_string, name, max_limit_string),
+            ));
+        }
+        // List
+        if let Some(list_string) = &parameter.list_string {
+            rows.push(list_string.clone());
+        }
+        // Other constraints
+        for constraint_string in parameter.constraint_strings.iter() {
+            rows.push(("Constraint".to_string(), constraint_string.clone()));
+        }
+        // Length
+        if parameter.length_range != (None, None) {
+            let length_bound_string =
+                |length: Option<usize>| length.map_or(String::new(), |x| x.to_string());
+            rows.push((
+                "Length".to_string(),
+                format!(
+                    "{}..{}",
+                    length_bound_string(parameter.length_range.0),
+                    length_bound_string(parameter.length_range.1)
+                ),
+            ));
+        }
+        // Regex
+        #[cfg(feature = "regex")]
+        if let Some(regex) = &parameter.regex {
+            rows.push(("Regex".to_string(), regex.as_str().to_string()));
+        }
+        rows
+    }
+
+    fn print_markdown_core<T: Write>(
+        &self,
+        writer: &mut T,
+        show_invisible: bool,
+    ) -> std::io::Result<()> {
+        let escape = |string: &str| string.replace('|', "\\|").replace('\n', "<br>");
+        let mut writer = BufWriter::new(writer);
+        writeln!(
+            writer,
+            "| Name | Type | Default Value | Range/Constraint | Explanation |"
+        )?;
+        writeln!(writer, "|---|---|---|---|---|")?;
+        for (name, parameter) in self.parameter_list.iter() {
+            if parameter.unvisible && !show_invisible {
+                continue;
+            }
+            let name_cell = if parameter.unvisible {
+                format!("{} (hidden)", name)
+            } else {
+                name.clone()
+            };
+            let condition_cell = ParameterBox::condition_rows(name, parameter)
+                .iter()
+                .map(|(label, condition)| format!("{}: {}", label, condition))
+                .collect::<Vec<String>>()
+                .join("; ");
+            writeln!(
+                writer,
+                "| {} | {} | {} | {} | {} |",
+                escape(&name_cell),
+                escape(&parameter.type_string),
+                escape(parameter.value_string.as_deref().unwrap_or("")),
+                escape(&condition_cell),
+                escape(parameter.explanation.as_deref().unwrap_or(""))
+            )?;
+        }
+        writer.flush()
+    }
+
+    fn print_json_core<T: Write>(&self, writer: &mut T) -> std::io::Result<()> {
+        let json_string = |string: &Option<String>| match string {
+            Some(string) => ParameterBox::escape_json(string),
+            None => "null".to_string(),
+        };
+        let mut writer = BufWriter::new(writer);
+        write!(writer, "{{")?;
+        for (i, (name, parameter)) in self.parameter_list.iter().enumerate() {
+            if i > 0 {
+                write!(writer, ",")?;
+            }
+            writeln!(writer)?;
+            writeln!(writer, "  {}: {{", ParameterBox::escape_json(name))?;
+            writeln!(
+                writer,
+                r#"    "type": {},"#,
+                ParameterBox::escape_json(&parameter.type_string)
+            )?;
+            writeln!(
+                writer,
+                r#"    "value": {},"#,
+                json_string(&parameter.value_string)
+            )?;
+            writeln!(
+                writer,
+                r#"    "range_min": {},"#,
+                json_string(&parameter.range_string.0)
+            )?;
+            writeln!(
+                writer,
+                r#"    "range_max": {},"#,
+                json_string(&parameter.range_string.1)
+            )?;
+            let (list_type, list_values) = match &parameter.list_string {
+                Some(list_string) => (
+                    Some(list_string.0.to_lowercase()),
+                    format!(
+                        "[{}]",
+                        parameter
+                            .list_value_strings
+                            .iter()
+                            .map(|value_string| ParameterBox::escape_json(value_string))
+                            .collect::<Vec<String>>()
+                            .join(", ")
+                    ),
+                ),
+                None => (None, "null".to_string()),
+            };
+            writeln!(writer, r#"    "list_type": {},"#, json_string(&list_type))?;
+            writeln!(writer, r#"    "list_values": {},"#, list_values)?;
+            writeln!(
+                writer,
+                r#"    "explanation": {},"#,
+                json_string(&parameter.explanation)
+            )?;
+            writeln!(writer, r#"    "visible": {},"#, !parameter.unvisible)?;
+            writeln!(writer, r#"    "required": {}"#, parameter.required)?;
+            write!(writer, "  }}")?;
+        }
+        if !self.parameter_list.is_empty() {
+            writeln!(writer)?;
+        }
+        writeln!(writer, "}}")?;
+        writer.flush()
+    }
+
+    /// Returns `{prefix}_{NAME}`, where characters of `name` which cannot be used in shell
+    /// identifiers are replaced with `_`.
+    fn env_var_name(prefix: &str, name: &str) -> String {
+        let name: String = name
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() || c == '_' {
+                    c.to_ascii_uppercase()
+                } else {
+                    '_'
+                }
+            })
+            .collect();
+        format!("{}_{}", prefix, name)
+    }
+
+    fn quote_shell(string: &str) -> String {
+        let is_plain = !string.is_empty()
+            && string
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || "_-+.,:/@%".contains(c));
+        if is_plain {
+            string.to_string()
         } else {
-            Err(ParameterBoxError::InvalidInputFile(err_msg))
+            format!("'{}'", string.replace('\'', r#"'\''"#))
         }
     }
 
-    #[track_caller]
-    pub fn print<T: Write>(&self, writer: &mut T) -> Result<(), ParameterBoxError> {
-        match self.print_core(writer) {
-            Ok(()) => Ok(()),
-            Err(io_error) => Err(io_error.into()),
+    fn escape_json(string: &str) -> String {
+        let mut escaped = String::with_capacity(string.len() + 2);
+        escaped.push('"');
+        for c in string.chars() {
+            match c {
+                '"' => escaped.push_str("\\\""),
+                '\\' => escaped.push_str("\\\\"),
+                '\n' => escaped.push_str("\\n"),
+                '\r' => escaped.push_str("\\r"),
+                '\t' => escaped.push_str("\\t"),
+                c if (c as u32) < 0x20 => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+                _ => escaped.push(c),
+            }
         }
+        escaped.push('"');
+        escaped
     }
 
-    #[track_caller]
-    fn set_value_by_string<T>(
-        &mut self,
-        name: &str,
-        value_string: &str,
-    ) -> Result<(), ParameterBoxError>
-    where
-        T: 'static + PartialOrd + PartialEq + Clone + Display + FromStr,
-    {
-        match T::from_str(value_string) {
-            Ok(value) => self.set_value(name, value),
-            Err(_) => Err(ParameterBoxError::InvalidParse(format!(
-                "{} cannot parse to {}.",
-                err_msg_header!(),
-                std::any::type_name::<T>(),
-            ))),
+    fn escape_xml(string: &str) -> String {
+        let mut escaped = String::with_capacity(string.len());
+        for c in string.chars() {
+            match c {
+                '&' => escaped.push_str("&amp;"),
+                '<' => escaped.push_str("&lt;"),
+                '>' => escaped.push_str("&gt;"),
+                '"' => escaped.push_str("&quot;"),
+                '\'' => escaped.push_str("&apos;"),
+                _ => escaped.push(c),
+            }
         }
+        escaped
     }
 
+    /// Updates the core of a parameter with a constraint shown as `constraint_string` and checks
+    /// the current value by `check`.
     #[track_caller]
-    fn set_range<T>(
+    fn set_core_constraint<T, F, C>(
         &mut self,
         name: &str,
-        range: (RangeCondition<T>, RangeCondition<T>),
+        constraint_string: String,
+        update: F,
+        check: C,
     ) -> Result<(), ParameterBoxError>
     where
         T: 'static + PartialOrd + PartialEq + Clone + Display,
+        F: FnOnce(&mut ParameterCore<T>),
+        C: Fn(&ParameterCore<T>) -> Result<(), String>,
     {
         if let Some(parameter) = self.parameter_list.get_mut(name) {
-            let mut error_sequence = false;
-            let mut err_msg = String::new();
-            let mut new_parameter_core = Box::new(
-                parameter
-                    .parameter_core
-                    .as_ref()
-                    .downcast_ref::<ParameterCore<T>>()
-                    .expect("Downcast failed.")
-                    .clone(),
-            );
-            parameter.range_string.0 = ParameterBox::make_min_limit_string(&range.0);
-            parameter.range_string.1 = ParameterBox::make_max_limit_string(&range.1);
-            new_parameter_core.range = (Some(range.0), Some(range.1));
-            if let Some(value) = &new_parameter_core.value {
-                if let Err(RangeError::LessThanMinLimit(condition)) =
-                    new_parameter_core.check_min_limit()
-                {
+            if parameter.passthrough {
+                self.error_counter += 1;
+                return Err(ParameterBoxError::InvalidCondition(err_msg_passthrough!(
+                    name
+                )));
+            }
+            let mut new_parameter_core = match ParameterBox::downcast_core::<T>(name, parameter) {
+                Ok(parameter_core) => Box::new(parameter_core.clone()),
+                Err(err) => {
                     self.error_counter += 1;
-                    ParameterBox::sequence_err_or_not(&mut error_sequence, &mut err_msg);
-                    err_msg.push_str(err_msg_bad_condition!(name, value, condition));
+                    return Err(err);
                 }
-                if let Err(RangeError::LargerThanMaxLimit(condition)) =
-                    new_parameter_core.check_max_limit()
-                {
+            };
+            update(&mut new_parameter_core);
+            let result = match check(&new_parameter_core) {
+                Ok(()) => Ok(()),
+                Err(condition) => {
                     self.error_counter += 1;
-                    ParameterBox::sequence_err_or_not(&mut error_sequence, &mut err_msg);
-                    err_msg.push_str(err_msg_bad_condition!(name, value, condition));
+                    Err(ParameterBoxError::InvalidCondition(
+                        err_msg_bad_condition!(
+                            name,
+                            parameter.value_string.as_ref().unwrap(),
+                            condition
+                        )
+                        .to_string(),
+                    ))
                 }
+            };
+            if !parameter.constraint_strings.contains(&constraint_string) {
+                parameter.constraint_strings.push(constraint_string);
             }
             parameter.parameter_core = new_parameter_core;
-            if err_msg.is_empty() {
-                Ok(())
-            } else {
-                Err(ParameterBoxError::InvalidCondition(err_msg))
-            }
+            result
         } else {
             self.error_counter += 1;
             Err(ParameterBoxError::NotAdded(err_msg_not_added!(name)))
         }
     }
 
+    /// Updates the given sides of the length range and checks the current value.
     #[track_caller]
-    fn set_min_limit<T>(
+    fn set_length_range(
         &mut self,
         name: &str,
-        min_limit: RangeCondition<T>,
-    ) -> Result<(), ParameterBoxError>
-    where
-        T: 'static + PartialOrd + PartialEq + Clone + Display,
-    {
-        if let Some(parameter) = self.parameter_list.get_mut(name) {
-            let mut err_msg = String::new();
-            let mut new_parameter_core = Box::new(
-                parameter
-                    .parameter_core
-                    .as_ref()
-                    .downcast_ref::<ParameterCore<T>>()
-                    .expect("Downcast failed.")
-                    .clone(),
-            );
-            parameter.range_string.0 = ParameterBox::make_min_limit_string(&min_limit);
-            new_parameter_core.range.0 = Some(min_limit);
-            if let Some(value) = &new_parameter_core.value {
-                if let Err(RangeError::LessThanMinLimit(condition)) =
-                    new_parameter_core.check_min_limit()
-                {
-                    self.error_counter += 1;
-                    err_msg.push_str(err_msg_bad_condition!(name, value, condition));
-                }
-            }
-            parameter.parameter_core = new_parameter_core;
-            if err_msg.is_empty() {
-                Ok(())
-            } else {
-                Err(ParameterBoxError::InvalidCondition(err_msg))
-            }
-        } else {
-            self.error_counter += 1;
-            Err(ParameterBoxError::NotAdded(err_msg_not_added!(name)))
+        length_range: (Option<usize>, Option<usize>),
+    ) -> Result<(), ParameterBoxError> {
+        self.check_string_constraint_target(name, "length constraints")?;
+        let parameter = self.parameter_list.get_mut(name).unwrap();
+        if length_range.0.is_some() {
+            parameter.length_range.0 = length_range.0;
         }
+        if length_range.1.is_some() {
+            parameter.length_range.1 = length_range.1;
+        }
+        self.check_string_conditions(name)
     }
 
+    /// Returns an error unless `name` is a `String` parameter which accepts `constraint`.
     #[track_caller]
-    fn set_max_limit<T>(
+    fn check_string_constraint_target(
         &mut self,
         name: &str,
-        max_limit: RangeCondition<T>,
-    ) -> Result<(), ParameterBoxError>
-    where
-        T: 'static + PartialOrd + PartialEq + Clone + Display,
-    {
-        if let Some(parameter) = self.parameter_list.get_mut(name) {
-            let mut err_msg = String::new();
-            let mut new_parameter_core = Box::new(
-                parameter
-                    .parameter_core
-                    .as_ref()
-                    .downcast_ref::<ParameterCore<T>>()
-                    .expect("Downcast failed.")
-                    .clone(),
-            );
-            parameter.range_string.1 = ParameterBox::make_max_limit_string(&max_limit);
-            new_parameter_core.range.1 = Some(max_limit);
-            if let Some(value) = &new_parameter_core.value {
-                if let Err(RangeError::LargerThanMaxLimit(condition)) =
-                    new_parameter_core.check_max_limit()
-                {
-                    self.error_counter += 1;
-                    err_msg.push_str(err_msg_bad_condition!(name, value, condition));
-                }
-            }
-            parameter.parameter_core = new_parameter_core;
-            if err_msg.is_empty() {
-                Ok(())
-            } else {
-                Err(ParameterBoxError::InvalidCondition(err_msg))
-            }
+        constraint: &str,
+    ) -> Result<(), ParameterBoxError> {
+        let parameter = if let Some(parameter) = self.parameter_list.get(name) {
+            parameter
         } else {
             self.error_counter += 1;
-            Err(ParameterBoxError::NotAdded(err_msg_not_added!(name)))
+            return Err(ParameterBoxError::NotAdded(err_msg_not_added!(name)));
+        };
+        if parameter.passthrough {
+            self.error_counter += 1;
+            return Err(ParameterBoxError::InvalidCondition(err_msg_passthrough!(
+                name
+            )));
+        }
+        if parameter.type_id != std::any::TypeId::of::<String>() {
+            self.error_counter += 1;
+            return Err(ParameterBoxError::InvalidCondition(format!(
+                "{} the type of `{}` is {}, so {} cannot be set.",
+                err_msg_header!(),
+                name,
+                parameter.type_string,
+                constraint
+            )));
         }
+        Ok(())
     }
 
+    /// Checks the current value of a `String` parameter against its string conditions.
     #[track_caller]
-    fn set_list_info<T>(
-        &mut self,
-        name: &str,
-        list: ListCondition<T>,
-    ) -> Result<(), ParameterBoxError>
-    where
-        T: 'static + PartialOrd + PartialEq + Clone + Display,
-    {
-        if let Some(parameter) = self.parameter_list.get_mut(name) {
-            let mut err_msg = String::new();
-            let mut new_parameter_core = Box::new(
-                parameter
-                    .parameter_core
-                    .as_ref()
-                    .downcast_ref::<ParameterCore<T>>()
-                    .expect("Downcast failed.")
-                    .clone(),
-            );
-            parameter.list_string = ParameterBox::make_list_info_string(&list);
-            new_parameter_core.list = Some(list);
-            if let Some(value) = &new_parameter_core.value {
-                match new_parameter_core.check_list_condition() {
-                    Err(ListError::BlacklistViolation(condition)) => {
-                        self.error_counter += 1;
-                        err_msg.push_str(err_msg_bad_condition!(name, value, condition));
-                    }
-                    Err(ListError::WhitelistViolation(condition)) => {
-                        self.error_counter += 1;
-                        err_msg.push_str(err_msg_bad_condition!(name, value, condition));
-                    }
-                    Ok(()) => (),
-                }
-            }
-            parameter.parameter_core = new_parameter_core;
-            if err_msg.is_empty() {
-                Ok(())
-            } else {
-                Err(ParameterBoxError::InvalidCondition(err_msg))
+    fn check_string_conditions(&mut self, name: &str) -> Result<(), ParameterBoxError> {
+        let parameter = &self.parameter_list[name];
+        let mut error_sequence = false;
+        let mut err_msg = String::new();
+        if let Some(value) = &parameter.value_string {
+            for condition in ParameterBox::violated_string_conditions(parameter, value) {
+                self.error_counter += 1;
+                ParameterBox::sequence_err_or_not(&mut error_sequence, &mut err_msg);
+                err_msg.push_str(err_msg_bad_condition!(name, value, condition));
             }
+        }
+        if err_msg.is_empty() {
+            Ok(())
         } else {
-            self.error_counter += 1;
-            Err(ParameterBoxError::NotAdded(err_msg_not_added!(name)))
+            Err(ParameterBoxError::InvalidCondition(err_msg))
         }
     }
 
-    fn print_core<T: Write>(&self, writer: &mut T) -> std::io::Result<()> {
-        let mut writer = BufWriter::new(writer);
-        for name in self.added_order.iter() {
-            let parameter = self.parameter_list.get(name).unwrap();
-            if parameter.unvisible {
-                continue;
-            }
-            // Name
-            writeln!(writer, "{}\n----------------------------", name)?;
-            // Type
-            writeln!(writer, "{:14}| {}", "Type", &parameter.type_string)?;
-            // Value
-            if let Some(value_string) = &parameter.value_string {
-                writeln!(writer, "{:14}| {}", "Default value", value_string)?;
-            }
-            // Range
-            match &parameter.range_string {
-                (Some(min_limit_string), Some(max_limit_string)) => {
-                    writeln!(
-                        writer,
-                        "{:14}| {} {} {}",
-                        "Range", min_limit_string, name, max_limit_string
-                    )?;
-                }
-                (Some(min_limit_string), None) => {
-                    writeln!(writer, "{:14}| {} {}", "Range", min_limit_string, name)?;
-                }
-                (None, Some(max_limit_string)) => {
-                    writeln!(writer, "{:14}| {} {}", "Range", name, max_limit_string)?;
-                }
-                (None, None) => (),
+    fn violated_string_conditions(parameter: &Parameter, value: &str) -> Vec<String> {
+        let mut conditions = Vec::new();
+        if let Some(min) = parameter.length_range.0 {
+            if value.len() < min {
+                conditions.push(format!("has at least {} bytes", min));
             }
-            // List
-            if let Some(list_string) = &parameter.list_string {
-                writeln!(writer, "{:14}| {}", &list_string.0, &list_string.1)?;
+        }
+        if let Some(max) = parameter.length_range.1 {
+            if value.len() > max {
+                conditions.push(format!("has at most {} bytes", max));
             }
-            // Explanation
-            if let Some(explanation) = &parameter.explanation {
-                writeln!(writer, "{:14}| {}", "Explanation", explanation)?;
+        }
+        #[cfg(feature = "regex")]
+        if let Some(regex) = &parameter.regex {
+            if !regex.is_match(value) {
+                conditions.push(format!("matches the regex `{}`", regex.as_str()));
             }
-            writeln!(writer, "")?;
         }
-        writer.flush()
+        conditions
     }
 
     fn make_min_limit_string<T>(min_limit: &RangeCondition<T>) -> Option<String>
@@ -857,11 +3506,11 @@ impl ParameterBox {
                         .join(", "),
                 ),
             )),
-            ListCondition::White(blacklist) => Some((
+            ListCondition::White(whitelist) => Some((
                 "Whitelist".to_string(),
                 format!(
-                    "Whitelist: [{}]",
-                    blacklist
+                    "[{}]",
+                    whitelist
                         .iter()
                         .map(|x| format!("{}", x))
                         .collect::<Vec<String>>()
@@ -871,6 +3520,95 @@ impl ParameterBox {
         }
     }
 
+    fn make_list_value_strings<T>(list: &ListCondition<T>) -> Vec<String>
+    where
+        T: 'static + PartialOrd + PartialEq + Clone + Display,
+    {
+        match list {
+            ListCondition::Black(list) | ListCondition::White(list) => {
+                list.iter().map(|x| format!("{}", x)).collect()
+            }
+        }
+    }
+
+    /// Removes the quotes around `string`.
+    ///
+    /// Returns `None` if `string` is not a single quoted string.
+    fn unquote(string: &str) -> Option<String> {
+        let mut chars = string.chars();
+        let quote = chars.next()?;
+        if quote != '"' && quote != '\'' {
+            return None;
+        }
+        let mut unquoted = String::new();
+        while let Some(c) = chars.next() {
+            if c == quote {
+                return if chars.next().is_none() {
+                    Some(unquoted)
+                } else {
+                    None
+                };
+            }
+            if c == '\\' && quote == '"' {
+                match chars.next()? {
+                    'n' => unquoted.push('\n'),
+                    't' => unquoted.push('\t'),
+                    escaped => unquoted.push(escaped),
+                }
+            } else {
+                unquoted.push(c);
+            }
+        }
+        None
+    }
+
+    /// Returns the core of `parameter` as `ParameterCore<T>`, or `TypeMismatch` for another type.
+    fn downcast_core<'a, T>(
+        name: &str,
+        parameter: &'a Parameter,
+    ) -> Result<&'a ParameterCore<T>, ParameterBoxError>
+    where
+        T: 'static + PartialOrd + PartialEq + Clone + Display,
+    {
+        parameter
+            .parameter_core
+            .as_any()
+            .downcast_ref::<ParameterCore<T>>()
+            .ok_or_else(|| ParameterBoxError::TypeMismatch {
+                name: name.to_string(),
+                requested: std::any::type_name::<T>().to_string(),
+                actual: parameter.type_string.clone(),
+            })
+    }
+
+    /// Removes a comment starting with `#` outside quotes from the end of `value_part`.
+    fn strip_inline_comment(value_part: &str) -> &str {
+        let mut chars = value_part.char_indices();
+        let end = match chars.next() {
+            Some((_, quote)) if quote == '"' || quote == '\'' => {
+                let mut closing_end = None;
+                while let Some((index, c)) = chars.next() {
+                    if c == '\\' && quote == '"' {
+                        chars.next();
+                    } else if c == quote {
+                        closing_end = Some(index + c.len_utf8());
+                        break;
+                    }
+                }
+                match closing_end {
+                    Some(closing_end)
+                        if value_part[closing_end..].trim_start().starts_with('#') =>
+                    {
+                        closing_end
+                    }
+                    _ => value_part.len(),
+                }
+            }
+            _ => value_part.find('#').unwrap_or(value_part.len()),
+        };
+        value_part[..end].trim_end()
+    }
+
     fn sequence_err_or_not(error_sequence: &mut bool, err_msg: &mut String) {
         if *error_sequence {
             err_msg.push('\n');
@@ -880,10 +3618,64 @@ impl ParameterBox {
     }
 }
 
+impl Default for ParameterBox {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ParameterBoxError {
+    /// Returns the original I/O error of `IoError`.
+    pub fn io_error(&self) -> Option<&std::io::Error> {
+        match self {
+            ParameterBoxError::IoError(_, io_error) => Some(io_error),
+            _ => None,
+        }
+    }
+
+    /// Returns the kind of the original I/O error of `IoError`.
+    pub fn io_error_kind(&self) -> Option<std::io::ErrorKind> {
+        self.io_error().map(|io_error| io_error.kind())
+    }
+}
+
 impl From<std::io::Error> for ParameterBoxError {
     #[track_caller]
     fn from(err: std::io::Error) -> ParameterBoxError {
-        ParameterBoxError::IoError(format!("{} {}", err_msg_header!(), err))
+        ParameterBoxError::IoError(format!("{} {}", err_msg_header!(), err), Arc::new(err))
+    }
+}
+
+impl Display for ParameterBoxError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            ParameterBoxError::InvalidCondition(err_msg)
+            | ParameterBoxError::AlreadyAdded(err_msg)
+            | ParameterBoxError::NotAdded(err_msg)
+            | ParameterBoxError::InvalidParse(err_msg)
+            | ParameterBoxError::InvalidInputFile(err_msg)
+            | ParameterBoxError::IoError(err_msg, _)
+            | ParameterBoxError::HelpRequested(err_msg) => f.write_str(err_msg),
+            ParameterBoxError::NotSet(name) => write!(f, "`{}` does not have a value.", name),
+            ParameterBoxError::TypeMismatch {
+                name,
+                requested,
+                actual,
+            } => write!(
+                f,
+                "`{}` is accessed as {}, but its type is {}.",
+                name, requested, actual
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ParameterBoxError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ParameterBoxError::IoError(_, io_error) => Some(io_error.as_ref()),
+            _ => None,
+        }
     }
 }
 
@@ -892,6 +3684,7 @@ mod test {
     use super::*;
 
     #[test]
+    #[allow(clippy::assertions_on_constants)]
     fn set_range_works() {
         let mut param_box = ParameterBox::new();
         param_box.add::<i32>("p1").unwrap();
@@ -900,4 +3693,30 @@ mod test {
             Ok(_) => assert!(true),
         }
     }
+
+    #[test]
+    fn read_stdin_core_works() {
+        let mut param_box = ParameterBox::new();
+        param_box.add::<f64>("lr").unwrap();
+        param_box.add::<u32>("epochs").unwrap();
+        param_box
+            .read_stdin_core("# comment\nlr 0.5\nepochs 10\n".as_bytes())
+            .unwrap();
+        assert_eq!(param_box.clone_value::<f64>("lr").unwrap(), Some(0.5));
+        assert_eq!(param_box.clone_value::<u32>("epochs").unwrap(), Some(10));
+    }
+
+    #[test]
+    fn read_stdin_core_reports_unknown_names() {
+        let mut param_box = ParameterBox::new();
+        param_box.add::<f64>("lr").unwrap();
+        match param_box.read_stdin_core("lr 0.5\nunknown 1\n".as_bytes()) {
+            Err(ParameterBoxError::InvalidInputFile(err_msg)) => {
+                assert!(err_msg.contains("`unknown`"));
+                assert!(err_msg.contains("'<stdin>'"));
+            }
+            _ => panic!("an unknown name must be reported"),
+        }
+        assert_eq!(param_box.clone_value::<f64>("lr").unwrap(), Some(0.5));
+    }
 }
@@ -0,0 +1,43 @@
+// Copyright (c) 2021 Yuichi Ishida
+//
+// Released under the MIT license.
+// see https://opensource.org/licenses/mit-license.php
+
+use core::fmt::Display;
+
+/// Numeric parameter types, which can be constrained by `set_nonzero` and the sign constraints.
+pub trait Numeric: 'static + PartialOrd + PartialEq + Clone + Display {
+    fn zero() -> Self;
+}
+
+macro_rules! impl_numeric {
+    ($zero:expr; $($type:ty),+) => {
+        $(
+            impl Numeric for $type {
+                fn zero() -> Self {
+                    $zero
+                }
+            }
+        )+
+    };
+}
+
+impl_numeric!(0; u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+impl_numeric!(0.0; f32, f64);
+
+/// Floating-point parameter types, which can be constrained by `set_finite_only`.
+pub trait Float: Numeric {
+    fn is_finite(&self) -> bool;
+}
+
+impl Float for f32 {
+    fn is_finite(&self) -> bool {
+        f32::is_finite(*self)
+    }
+}
+
+impl Float for f64 {
+    fn is_finite(&self) -> bool {
+        f64::is_finite(*self)
+    }
+}
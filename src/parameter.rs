@@ -3,8 +3,9 @@
 // Released under the MIT license.
 // see https://opensource.org/licenses/mit-license.php
 
-use core::fmt::Display;
+use core::fmt::{Debug, Display};
 use std::any::{type_name, Any, TypeId};
+use std::rc::Rc;
 
 #[derive(Debug, Clone)]
 pub struct ParameterCore<T: PartialOrd + PartialEq + Clone + Display> {
@@ -16,20 +17,72 @@ pub struct ParameterCore<T: PartialOrd + PartialEq + Clone + Display> {
     pub range: (Option<RangeCondition<T>>, Option<RangeCondition<T>>),
     /// Black list or white list of a parameter
     pub list: Option<ListCondition<T>>,
+    /// Zero of `T`, which the value must differ from if set by `set_nonzero`.
+    pub excluded_zero: Option<T>,
+    /// Finiteness check of the value, set by `set_finite_only`.
+    pub require_finite: Option<fn(&T) -> bool>,
 }
 
-#[derive(Debug)]
+/// Type-erased operations on `ParameterCore<T>`.
+///
+/// `Parameter` keeps its core behind this trait so that operations which do not know `T` can still
+/// be performed. Use `as_any` to downcast to the concrete `ParameterCore<T>`.
+pub trait AnyParameterCore {
+    fn as_any(&self) -> &dyn Any;
+    fn box_clone(&self) -> Box<dyn AnyParameterCore>;
+    /// Returns a copy of `self` whose range, list, nonzero and finiteness checks are replaced with
+    /// those of `source`.
+    ///
+    /// `source` must have the same type as `self`.
+    fn with_constraints_of(&self, source: &dyn AnyParameterCore) -> Box<dyn AnyParameterCore>;
+    /// Returns the conditions which the current value does not satisfy.
+    fn violated_conditions(&self) -> Vec<String>;
+    /// Returns a copy of `self` without its value.
+    fn without_value(&self) -> Box<dyn AnyParameterCore>;
+    /// Returns a copy of `self` without its minimum limit.
+    fn without_min_limit(&self) -> Box<dyn AnyParameterCore>;
+    /// Returns a copy of `self` without its maximum limit.
+    fn without_max_limit(&self) -> Box<dyn AnyParameterCore>;
+    /// Returns a copy of `self` without its blacklist or whitelist.
+    fn without_list(&self) -> Box<dyn AnyParameterCore>;
+    /// Returns the current value as `&T` behind `&dyn Any`.
+    fn value_as_any(&self) -> Option<&dyn Any>;
+}
+
+#[derive(Debug, Clone)]
 pub struct Parameter {
-    pub parameter_core: Box<dyn Any>,
+    pub parameter_core: Box<dyn AnyParameterCore>,
     pub type_id: TypeId,
     pub type_string: String,
     pub value_string: Option<String>,
+    pub default_string: Option<String>,
     pub range_string: (Option<String>, Option<String>),
     pub list_string: Option<(String, String)>,
+    /// Display strings of the values in the blacklist or whitelist.
+    pub list_value_strings: Vec<String>,
     pub explanation: Option<String>,
     pub unvisible: bool,
+    pub passthrough: bool,
+    pub required: bool,
+    pub validator: Option<Validator>,
+    /// Minimum and maximum byte lengths of a `String` value.
+    pub length_range: (Option<usize>, Option<usize>),
+    #[cfg(feature = "regex")]
+    pub regex: Option<regex::Regex>,
+    /// Display strings of constraints other than the range and the list, e.g. `≠ 0`.
+    pub constraint_strings: Vec<String>,
+    pub section: Option<String>,
+    /// Check of the warning range, which returns the violated condition as `Err`.
+    pub warning_range: Option<Validator>,
+    pub warning_range_string: (Option<String>, Option<String>),
 }
 
+pub type ValidatorFn = dyn Fn(&dyn Any) -> Result<(), String>;
+
+/// Type-erased closure set by `ParameterBox::set_validator`, which receives `&T` as `&dyn Any`.
+#[derive(Clone)]
+pub struct Validator(pub Rc<ValidatorFn>);
+
 #[derive(Debug, Clone)]
 pub enum RangeCondition<T: PartialOrd + PartialEq + Clone> {
     Open(T),
@@ -63,6 +116,8 @@ where
             value: None,
             range: (None, None),
             list: None,
+            excluded_zero: None,
+            require_finite: None,
         }
     }
 
@@ -85,7 +140,7 @@ where
                 }
             }
         }
-        return Ok(());
+        Ok(())
     }
 
     pub fn check_max_limit(&self) -> Result<(), RangeError> {
@@ -107,7 +162,7 @@ where
                 }
             }
         }
-        return Ok(());
+        Ok(())
     }
 
     pub fn check_list_condition(&self) -> Result<(), ListError> {
@@ -129,14 +184,32 @@ where
                 }
             }
         }
-        return Ok(());
+        Ok(())
+    }
+
+    pub fn check_nonzero(&self) -> Result<(), String> {
+        if let (Some(value), Some(zero)) = (&self.value, &self.excluded_zero) {
+            if value == zero {
+                return Err(ParameterCore::err_msg_bad_range("≠", zero));
+            }
+        }
+        Ok(())
+    }
+
+    pub fn check_finite(&self) -> Result<(), String> {
+        if let (Some(value), Some(is_finite)) = (&self.value, &self.require_finite) {
+            if !is_finite(value) {
+                return Err("is finite".to_string());
+            }
+        }
+        Ok(())
     }
 
     fn err_msg_bad_range(condition: &str, limit: &T) -> String {
         format!("{} {}", condition, limit)
     }
 
-    fn err_msg_bad_list(condition: &str, list: &Vec<T>) -> String {
+    fn err_msg_bad_list(condition: &str, list: &[T]) -> String {
         format!(
             "{} [{}]",
             condition,
@@ -148,6 +221,100 @@ where
     }
 }
 
+impl<T> AnyParameterCore for ParameterCore<T>
+where
+    T: 'static + PartialOrd + PartialEq + Clone + Display,
+{
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn box_clone(&self) -> Box<dyn AnyParameterCore> {
+        Box::new(self.clone())
+    }
+
+    fn with_constraints_of(&self, source: &dyn AnyParameterCore) -> Box<dyn AnyParameterCore> {
+        let source = source
+            .as_any()
+            .downcast_ref::<ParameterCore<T>>()
+            .expect("Downcast failed.");
+        let mut new_parameter_core = Box::new(self.clone());
+        new_parameter_core.range = source.range.clone();
+        new_parameter_core.list = source.list.clone();
+        new_parameter_core.excluded_zero = source.excluded_zero.clone();
+        new_parameter_core.require_finite = source.require_finite;
+        new_parameter_core
+    }
+
+    fn violated_conditions(&self) -> Vec<String> {
+        let mut conditions = Vec::new();
+        if let Err(RangeError::LessThanMinLimit(condition)) = self.check_min_limit() {
+            conditions.push(condition);
+        }
+        if let Err(RangeError::LargerThanMaxLimit(condition)) = self.check_max_limit() {
+            conditions.push(condition);
+        }
+        match self.check_list_condition() {
+            Err(ListError::BlacklistViolation(condition)) => conditions.push(condition),
+            Err(ListError::WhitelistViolation(condition)) => conditions.push(condition),
+            Ok(()) => (),
+        }
+        if let Err(condition) = self.check_nonzero() {
+            conditions.push(condition);
+        }
+        if let Err(condition) = self.check_finite() {
+            conditions.push(condition);
+        }
+        conditions
+    }
+
+    fn without_value(&self) -> Box<dyn AnyParameterCore> {
+        let mut new_parameter_core = Box::new(self.clone());
+        new_parameter_core.value = None;
+        new_parameter_core
+    }
+
+    fn without_min_limit(&self) -> Box<dyn AnyParameterCore> {
+        let mut new_parameter_core = Box::new(self.clone());
+        new_parameter_core.range.0 = None;
+        new_parameter_core
+    }
+
+    fn without_max_limit(&self) -> Box<dyn AnyParameterCore> {
+        let mut new_parameter_core = Box::new(self.clone());
+        new_parameter_core.range.1 = None;
+        new_parameter_core
+    }
+
+    fn without_list(&self) -> Box<dyn AnyParameterCore> {
+        let mut new_parameter_core = Box::new(self.clone());
+        new_parameter_core.list = None;
+        new_parameter_core
+    }
+
+    fn value_as_any(&self) -> Option<&dyn Any> {
+        self.value.as_ref().map(|value| value as &dyn Any)
+    }
+}
+
+impl Clone for Box<dyn AnyParameterCore> {
+    fn clone(&self) -> Self {
+        self.box_clone()
+    }
+}
+
+impl Debug for dyn AnyParameterCore {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("AnyParameterCore { .. }")
+    }
+}
+
+impl Debug for Validator {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("Validator { .. }")
+    }
+}
+
 impl Parameter {
     pub fn new<T>() -> Self
     where
@@ -158,10 +325,22 @@ impl Parameter {
             type_id: TypeId::of::<T>(),
             type_string: type_name::<T>().to_string(),
             value_string: None,
+            default_string: None,
             range_string: (None, None),
             list_string: None,
+            list_value_strings: Vec::new(),
             explanation: None,
             unvisible: false,
+            passthrough: false,
+            required: false,
+            validator: None,
+            length_range: (None, None),
+            #[cfg(feature = "regex")]
+            regex: None,
+            constraint_strings: Vec::new(),
+            section: None,
+            warning_range: None,
+            warning_range_string: (None, None),
         }
     }
 }
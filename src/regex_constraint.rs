@@ -0,0 +1,33 @@
+// Copyright (c) 2021 Yuichi Ishida
+//
+// Released under the MIT license.
+// see https://opensource.org/licenses/mit-license.php
+
+#[cfg(debug_assertions)]
+use std::panic::Location;
+
+use regex::Regex;
+
+use crate::{ParameterBox, ParameterBoxError};
+
+impl ParameterBox {
+    /// Sets a regex which values of a `String` parameter must match.
+    #[track_caller]
+    pub fn set_regex(&mut self, name: &str, pattern: &str) -> Result<(), ParameterBoxError> {
+        self.check_string_constraint_target(name, "regex constraints")?;
+        let regex = match Regex::new(pattern) {
+            Ok(regex) => regex,
+            Err(err) => {
+                self.error_counter += 1;
+                return Err(ParameterBoxError::InvalidCondition(format!(
+                    "{} `{}` is not a valid regex: {}",
+                    err_msg_header!(),
+                    pattern,
+                    err
+                )));
+            }
+        };
+        self.parameter_list.get_mut(name).unwrap().regex = Some(regex);
+        self.check_string_conditions(name)
+    }
+}
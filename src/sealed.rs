@@ -0,0 +1,95 @@
+// Copyright (c) 2021 Yuichi Ishida
+//
+// Released under the MIT license.
+// see https://opensource.org/licenses/mit-license.php
+
+#[cfg(debug_assertions)]
+use std::panic::Location;
+
+use core::fmt::Display;
+use std::io::Write;
+
+use crate::{ParameterBox, ParameterBoxError};
+
+/// Parameter box which cannot be modified, which is created by `ParameterBox::seal`.
+#[derive(Debug, Clone)]
+pub struct SealedParameterBox {
+    parameter_box: ParameterBox,
+}
+
+impl SealedParameterBox {
+    pub(crate) fn new(parameter_box: ParameterBox) -> Self {
+        Self { parameter_box }
+    }
+
+    /// Returns a reference to the value of `name`.
+    #[track_caller]
+    pub fn ref_value<T>(&self, name: &str) -> Result<&Option<T>, ParameterBoxError>
+    where
+        T: 'static + PartialOrd + PartialEq + Clone + Display,
+    {
+        self.parameter_box.ref_value(name)
+    }
+
+    /// Returns a clone of the value of `name`.
+    #[track_caller]
+    pub fn clone_value<T>(&self, name: &str) -> Result<Option<T>, ParameterBoxError>
+    where
+        T: 'static + PartialOrd + PartialEq + Clone + Display,
+    {
+        self.parameter_box.clone_value(name)
+    }
+
+    /// Returns a clone of the value of `name`, exiting the process if it cannot be returned.
+    #[track_caller]
+    pub fn clone_value_forcibly<T>(&self, name: &str) -> T
+    where
+        T: 'static + PartialOrd + PartialEq + Clone + Display,
+    {
+        match crate::unwrap_result!(self.clone_value::<T>(name)) {
+            Some(value) => value,
+            None => {
+                eprintln!("{} `{}` does not have a value.", err_msg_header!(), name);
+                std::process::exit(1);
+            }
+        }
+    }
+
+    /// Prints visible parameters in the same format as `ParameterBox::print`.
+    #[track_caller]
+    pub fn print<T: Write>(&self, writer: &mut T) -> Result<(), ParameterBoxError> {
+        self.parameter_box.print(writer)
+    }
+
+    /// Prints all parameters as a JSON object keyed by name.
+    #[track_caller]
+    pub fn print_json<T: Write>(&self, writer: &mut T) -> Result<(), ParameterBoxError> {
+        self.parameter_box.print_json(writer)
+    }
+
+    /// Returns whether `name` has a value.
+    #[track_caller]
+    pub fn is_set(&self, name: &str) -> Result<bool, ParameterBoxError> {
+        self.parameter_box.is_set(name)
+    }
+
+    /// Returns whether `name` has been added.
+    pub fn contains(&self, name: &str) -> bool {
+        self.parameter_box.contains(name)
+    }
+
+    /// Returns the names of parameters in the order they were added.
+    pub fn parameter_names(&self) -> impl Iterator<Item = &str> + '_ {
+        self.parameter_box.parameter_names()
+    }
+
+    /// Returns the number of parameters.
+    pub fn len(&self) -> usize {
+        self.parameter_box.len()
+    }
+
+    /// Returns whether the parameter box has no parameters.
+    pub fn is_empty(&self) -> bool {
+        self.parameter_box.is_empty()
+    }
+}
@@ -0,0 +1,152 @@
+// Copyright (c) 2021 Yuichi Ishida
+//
+// Released under the MIT license.
+// see https://opensource.org/licenses/mit-license.php
+
+#[cfg(feature = "serde_json")]
+use std::fs;
+#[cfg(all(feature = "serde_json", debug_assertions))]
+use std::panic::Location;
+
+use serde::ser::{Serialize, SerializeMap, SerializeStruct, Serializer};
+
+use crate::parameter::Parameter;
+use crate::ParameterBox;
+#[cfg(feature = "serde_json")]
+use crate::ParameterBoxError;
+
+/// Serializes parameters as a map keyed by name in the same shape as `print_json`.
+impl Serialize for ParameterBox {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(self.len()))?;
+        for (name, parameter) in self.parameter_list.iter() {
+            map.serialize_entry(name, &SerializedParameter(parameter))?;
+        }
+        map.end()
+    }
+}
+
+struct SerializedParameter<'a>(&'a Parameter);
+
+impl Serialize for SerializedParameter<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let parameter = self.0;
+        let (list_type, list_values) = match &parameter.list_string {
+            Some((list_type, _)) => (
+                Some(list_type.to_lowercase()),
+                Some(&parameter.list_value_strings),
+            ),
+            None => (None, None),
+        };
+        let mut state = serializer.serialize_struct("Parameter", 9)?;
+        state.serialize_field("type", &parameter.type_string)?;
+        state.serialize_field("value", &parameter.value_string)?;
+        state.serialize_field("range_min", &parameter.range_string.0)?;
+        state.serialize_field("range_max", &parameter.range_string.1)?;
+        state.serialize_field("list_type", &list_type)?;
+        state.serialize_field("list_values", &list_values)?;
+        state.serialize_field("explanation", &parameter.explanation)?;
+        state.serialize_field("visible", &!parameter.unvisible)?;
+        state.serialize_field("required", &parameter.required)?;
+        state.end()
+    }
+}
+
+#[cfg(feature = "serde_json")]
+impl ParameterBox {
+    /// Sets values from a JSON object keyed by parameter names.
+    ///
+    /// Each value is a string, a number or a boolean read in the same way as a value in a parameter
+    /// file, or an object whose `value` field is such a value as serialized from a parameter box.
+    /// `null` values are skipped.
+    #[track_caller]
+    pub fn populate_from_value(
+        &mut self,
+        value: &serde_json::Value,
+    ) -> Result<(), ParameterBoxError> {
+        let object = match value {
+            serde_json::Value::Object(object) => object,
+            _ => {
+                self.error_counter += 1;
+                return Err(ParameterBoxError::InvalidInputFile(format!(
+                    "{} parameters must be given as a JSON object.",
+                    err_msg_header!()
+                )));
+            }
+        };
+        let mut error_sequence = false;
+        let mut err_msg = String::new();
+        for (name, value) in object.iter() {
+            if !self.parameter_list.contains_key(name) {
+                self.error_counter += 1;
+                ParameterBox::sequence_err_or_not(&mut error_sequence, &mut err_msg);
+                err_msg.push_str(&err_msg_not_added!(name));
+                continue;
+            }
+            let value = match value {
+                serde_json::Value::Object(object) => {
+                    object.get("value").unwrap_or(&serde_json::Value::Null)
+                }
+                _ => value,
+            };
+            let value_string = match value {
+                serde_json::Value::Null => continue,
+                serde_json::Value::String(value_string) => value_string.clone(),
+                serde_json::Value::Bool(_) | serde_json::Value::Number(_) => value.to_string(),
+                _ => {
+                    self.error_counter += 1;
+                    ParameterBox::sequence_err_or_not(&mut error_sequence, &mut err_msg);
+                    err_msg.push_str(&format!(
+                        "{} `{}` = {} is not a JSON string, number or boolean.",
+                        err_msg_header!(),
+                        name,
+                        value
+                    ));
+                    continue;
+                }
+            };
+            match self.set_value_auto_from_string(name, &value_string) {
+                Ok(()) => (),
+                Err(ParameterBoxError::InvalidCondition(msg))
+                | Err(ParameterBoxError::InvalidParse(msg)) => {
+                    ParameterBox::sequence_err_or_not(&mut error_sequence, &mut err_msg);
+                    err_msg.push_str(&msg);
+                }
+                Err(_) => unreachable!(),
+            }
+        }
+        if err_msg.is_empty() {
+            Ok(())
+        } else {
+            Err(ParameterBoxError::InvalidInputFile(err_msg))
+        }
+    }
+
+    /// Returns the entries of a JSON object with `None` for values which are not strings.
+    #[track_caller]
+    pub(crate) fn read_json_explanations(
+        filename: &str,
+    ) -> Result<Vec<(String, Option<String>)>, ParameterBoxError> {
+        let content = fs::read_to_string(filename)?;
+        match serde_json::from_str::<serde_json::Value>(&content) {
+            Ok(serde_json::Value::Object(object)) => Ok(object
+                .into_iter()
+                .map(|(name, value)| match value {
+                    serde_json::Value::String(explanation) => (name, Some(explanation)),
+                    _ => (name, None),
+                })
+                .collect()),
+            Ok(_) => Err(ParameterBoxError::InvalidInputFile(format!(
+                "{} the file '{}' does not hold a JSON object.",
+                err_msg_header!(),
+                filename
+            ))),
+            Err(err) => Err(ParameterBoxError::InvalidInputFile(format!(
+                "{} the file '{}' is not a valid JSON file: {}",
+                err_msg_header!(),
+                filename,
+                err
+            ))),
+        }
+    }
+}
@@ -0,0 +1,269 @@
+// Copyright (c) 2021 Yuichi Ishida
+//
+// Released under the MIT license.
+// see https://opensource.org/licenses/mit-license.php
+
+use std::any::TypeId;
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+
+#[cfg(debug_assertions)]
+use std::panic::Location;
+
+use crate::{ParameterBox, ParameterBoxError};
+
+impl ParameterBox {
+    /// Creates a parameter box from a flat TOML file.
+    ///
+    /// The keys are either at the top level or in a `[parameters]` table which is the only
+    /// top-level item, as written by `write_toml_file`. Each key is added with the type inferred
+    /// from its value: integers are `i64`, floats are `f64`, booleans are `bool` and strings are
+    /// `String`.
+    #[track_caller]
+    pub fn from_toml_file(filename: &str) -> Result<ParameterBox, ParameterBoxError> {
+        let mut table = ParameterBox::read_toml_table(filename)?;
+        if table.len() == 1 {
+            if let Some(toml::Value::Table(parameters)) = table.remove("parameters") {
+                table = parameters;
+            }
+        }
+        let mut parameter_box = ParameterBox::new();
+        let mut error_sequence = false;
+        let mut err_msg = String::new();
+        for (name, value) in table.iter() {
+            match value {
+                toml::Value::Integer(value) => {
+                    parameter_box.add::<i64>(name)?;
+                    parameter_box.set_value::<i64>(name, *value)?;
+                }
+                toml::Value::Float(value) => {
+                    parameter_box.add::<f64>(name)?;
+                    parameter_box.set_value::<f64>(name, *value)?;
+                }
+                toml::Value::Boolean(value) => {
+                    parameter_box.add::<bool>(name)?;
+                    parameter_box.set_value::<bool>(name, *value)?;
+                }
+                toml::Value::String(value) => {
+                    parameter_box.add::<String>(name)?;
+                    parameter_box.set_value::<String>(name, value.clone())?;
+                }
+                _ => {
+                    parameter_box.error_counter += 1;
+                    ParameterBox::sequence_err_or_not(&mut error_sequence, &mut err_msg);
+                    err_msg.push_str(&format!(
+                        "{} in the file '{}', `{}` is a TOML {}, which cannot be read as a parameter.",
+                        err_msg_header!(),
+                        filename,
+                        name,
+                        value.type_str()
+                    ));
+                }
+            }
+        }
+        if err_msg.is_empty() {
+            Ok(parameter_box)
+        } else {
+            Err(ParameterBoxError::InvalidInputFile(err_msg))
+        }
+    }
+
+    /// Sets values from the `[parameters]` table of a TOML file.
+    ///
+    /// TOML integers are read into integer and floating-point parameters, TOML floats into
+    /// floating-point parameters, TOML booleans into `bool` parameters and TOML strings into
+    /// `String`, `char` and integer parameters.
+    #[track_caller]
+    pub fn read_toml_file(&mut self, filename: &str) -> Result<(), ParameterBoxError> {
+        let mut table = ParameterBox::read_toml_table(filename)?;
+        let parameters = match table.remove("parameters") {
+            Some(toml::Value::Table(parameters)) => parameters,
+            _ => {
+                self.error_counter += 1;
+                return Err(ParameterBoxError::InvalidInputFile(format!(
+                    "{} the file '{}' does not have a `[parameters]` table.",
+                    err_msg_header!(),
+                    filename
+                )));
+            }
+        };
+        let mut error_sequence = false;
+        let mut err_msg = String::new();
+        for (name, value) in parameters.iter() {
+            let type_id = if let Some(parameter) = self.parameter_list.get(name) {
+                parameter.type_id
+            } else {
+                self.error_counter += 1;
+                ParameterBox::sequence_err_or_not(&mut error_sequence, &mut err_msg);
+                err_msg.push_str(&format!(
+                    "{} in the file '{}', `{}` has not been added to a parameter box.",
+                    err_msg_header!(),
+                    filename,
+                    name
+                ));
+                continue;
+            };
+            let value_string = match value {
+                toml::Value::Integer(value)
+                    if ParameterBox::is_toml_integer_type(type_id)
+                        || ParameterBox::is_toml_float_type(type_id) =>
+                {
+                    value.to_string()
+                }
+                toml::Value::Float(value) if ParameterBox::is_toml_float_type(type_id) => {
+                    value.to_string()
+                }
+                toml::Value::Boolean(value) if type_id == TypeId::of::<bool>() => value.to_string(),
+                toml::Value::String(value)
+                    if type_id == TypeId::of::<String>()
+                        || type_id == TypeId::of::<char>()
+                        || ParameterBox::is_toml_integer_type(type_id) =>
+                {
+                    value.clone()
+                }
+                _ => {
+                    self.error_counter += 1;
+                    ParameterBox::sequence_err_or_not(&mut error_sequence, &mut err_msg);
+                    err_msg.push_str(&format!(
+                        "{} in the file '{}', `{}` is a TOML {}, which cannot be read as {}.",
+                        err_msg_header!(),
+                        filename,
+                        name,
+                        value.type_str(),
+                        self.parameter_list[name].type_string
+                    ));
+                    continue;
+                }
+            };
+            match self.set_value_auto_from_string(name, &value_string) {
+                Ok(()) => (),
+                Err(ParameterBoxError::InvalidCondition(msg))
+                | Err(ParameterBoxError::InvalidParse(msg)) => {
+                    ParameterBox::sequence_err_or_not(&mut error_sequence, &mut err_msg);
+                    err_msg.push_str(&format!("{} (in the file '{}')", msg, filename));
+                }
+                Err(_) => unreachable!(),
+            }
+        }
+        self.check_required_after_reading(
+            &format!("the file '{}'", filename),
+            &mut error_sequence,
+            &mut err_msg,
+        );
+        if err_msg.is_empty() {
+            Ok(())
+        } else {
+            Err(ParameterBoxError::InvalidInputFile(err_msg))
+        }
+    }
+
+    /// Writes parameters with values to the `[parameters]` table of a TOML file.
+    ///
+    /// Explanations are written as comment lines above the values. Parameters without values
+    /// are skipped, while invisible parameters are written. Integers out of the range of TOML
+    /// integers, which are `i64`, are written as strings.
+    #[track_caller]
+    pub fn write_toml_file(&self, filename: &str) -> Result<(), ParameterBoxError> {
+        let mut file = File::create(filename)?;
+        match self.write_toml_file_core(&mut file) {
+            Ok(()) => Ok(()),
+            Err(io_error) => Err(io_error.into()),
+        }
+    }
+
+    fn write_toml_file_core<T: Write>(&self, writer: &mut T) -> std::io::Result<()> {
+        let mut writer = BufWriter::new(writer);
+        writeln!(writer, "[parameters]")?;
+        for (name, parameter) in self.parameter_list.iter() {
+            if let Some(value_string) = &parameter.value_string {
+                if let Some(explanation) = &parameter.explanation {
+                    for explanation_line in explanation.lines() {
+                        writeln!(writer, "# {}", explanation_line)?;
+                    }
+                }
+                let type_id = parameter.type_id;
+                let toml_value = if type_id == TypeId::of::<bool>()
+                    || (ParameterBox::is_toml_integer_type(type_id)
+                        && value_string.parse::<i64>().is_ok())
+                {
+                    value_string.clone()
+                } else if ParameterBox::is_toml_float_type(type_id) {
+                    // Written through toml so that `1` becomes `1.0` and `NaN` becomes `nan`.
+                    let value = value_string
+                        .parse()
+                        .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+                    toml::Value::Float(value).to_string()
+                } else {
+                    // Integers out of the range of `i64` are also written as strings.
+                    toml::Value::String(value_string.clone()).to_string()
+                };
+                let is_bare_key = !name.is_empty()
+                    && name
+                        .chars()
+                        .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+                if is_bare_key {
+                    writeln!(writer, "{} = {}", name, toml_value)?;
+                } else {
+                    writeln!(
+                        writer,
+                        "{} = {}",
+                        toml::Value::String(name.clone()),
+                        toml_value
+                    )?;
+                }
+            }
+        }
+        writer.flush()
+    }
+
+    fn is_toml_integer_type(type_id: TypeId) -> bool {
+        [
+            TypeId::of::<u8>(),
+            TypeId::of::<u16>(),
+            TypeId::of::<u32>(),
+            TypeId::of::<u64>(),
+            TypeId::of::<u128>(),
+            TypeId::of::<usize>(),
+            TypeId::of::<i8>(),
+            TypeId::of::<i16>(),
+            TypeId::of::<i32>(),
+            TypeId::of::<i64>(),
+            TypeId::of::<i128>(),
+            TypeId::of::<isize>(),
+        ]
+        .contains(&type_id)
+    }
+
+    fn is_toml_float_type(type_id: TypeId) -> bool {
+        type_id == TypeId::of::<f32>() || type_id == TypeId::of::<f64>()
+    }
+
+    /// Returns the entries of a TOML table with `None` for values which are not strings.
+    #[track_caller]
+    pub(crate) fn read_toml_explanations(
+        filename: &str,
+    ) -> Result<Vec<(String, Option<String>)>, ParameterBoxError> {
+        let table = ParameterBox::read_toml_table(filename)?;
+        Ok(table
+            .into_iter()
+            .map(|(name, value)| match value {
+                toml::Value::String(explanation) => (name, Some(explanation)),
+                _ => (name, None),
+            })
+            .collect())
+    }
+
+    #[track_caller]
+    fn read_toml_table(filename: &str) -> Result<toml::Table, ParameterBoxError> {
+        let content = fs::read_to_string(filename)?;
+        match content.parse::<toml::Table>() {
+            Ok(table) => Ok(table),
+            Err(err) => Err(ParameterBoxError::InvalidInputFile(format!(
+                "{} the file '{}' is not a valid TOML file: {}",
+                err_msg_header!(),
+                filename,
+                err
+            ))),
+        }
+    }
+}
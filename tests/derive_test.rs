@@ -0,0 +1,64 @@
+#![cfg(feature = "derive")]
+
+use core::convert::TryFrom;
+use parameter_box::{ParameterBox, ParameterBoxError, ParameterBoxFrom};
+
+#[derive(ParameterBoxFrom, Debug, PartialEq)]
+struct Config {
+    x: f64,
+    y: i32,
+    label: String,
+}
+
+#[test]
+fn derive_parameter_box_from_works() {
+    let config = Config {
+        x: 0.5,
+        y: -3,
+        label: "run".to_string(),
+    };
+
+    let mut param_box = ParameterBox::from(config);
+    assert_eq!(param_box.clone_value::<f64>("x").unwrap().unwrap(), 0.5);
+    assert_eq!(param_box.clone_value::<i32>("y").unwrap().unwrap(), -3);
+    param_box.set_value::<i32>("y", 7).unwrap();
+
+    assert_eq!(
+        Config::try_from(param_box).unwrap(),
+        Config {
+            x: 0.5,
+            y: 7,
+            label: "run".to_string(),
+        }
+    );
+}
+
+#[test]
+fn try_from_returns_errors() {
+    let mut param_box = ParameterBox::new();
+    param_box.add::<f64>("x").unwrap();
+    param_box.add::<i32>("y").unwrap();
+    param_box.add::<String>("label").unwrap();
+    param_box.set_value::<f64>("x", 0.5).unwrap();
+    param_box
+        .set_value::<String>("label", "run".to_string())
+        .unwrap();
+    assert!(matches!(
+        Config::try_from(param_box),
+        Err(ParameterBoxError::NotSet(_))
+    ));
+
+    let mut param_box = ParameterBox::new();
+    param_box.add::<f64>("x").unwrap();
+    param_box.add::<u32>("y").unwrap();
+    param_box.add::<String>("label").unwrap();
+    param_box.set_value::<f64>("x", 0.5).unwrap();
+    param_box.set_value::<u32>("y", 7).unwrap();
+    param_box
+        .set_value::<String>("label", "run".to_string())
+        .unwrap();
+    assert!(matches!(
+        Config::try_from(param_box),
+        Err(ParameterBoxError::TypeMismatch { .. })
+    ));
+}
@@ -1,6 +1,10 @@
-use parameter_box::ParameterBox;
+use parameter_box::{
+    ListCondition, ParameterBox, ParameterBoxBuilder, ParameterBoxError, ParameterFileOptions,
+    ParameterInfo, ParameterView, RangeCondition, Separator, TypeErasedParam,
+};
 
 #[test]
+#[allow(clippy::approx_constant)]
 fn parameter_box_works() {
     let mut param_box = ParameterBox::new();
 
@@ -148,3 +152,2240 @@ fn parameter_box_works() {
     );
     assert_eq!(param_box.clone_value::<isize>("param_None").unwrap(), None);
 }
+
+#[test]
+fn add_passthrough_rejects_constraints() {
+    let mut param_box = ParameterBox::new();
+
+    param_box.add_passthrough::<String>("label").unwrap();
+    param_box.add_passthrough::<i32>("offset").unwrap();
+
+    assert!(param_box
+        .set_whitelist::<String>("label", vec!["a".to_string()])
+        .is_err());
+    assert!(param_box
+        .set_range_close_close::<i32>("offset", (0, 10))
+        .is_err());
+    assert!(param_box.set_min_limit_open::<i32>("offset", 0).is_err());
+    assert_eq!(param_box.get_num_errors(), 3);
+
+    param_box.set_value::<i32>("offset", -100).unwrap();
+    param_box
+        .set_value::<String>("label", "anything".to_string())
+        .unwrap();
+    assert_eq!(
+        param_box.clone_value::<i32>("offset").unwrap().unwrap(),
+        -100
+    );
+}
+
+#[test]
+fn set_limit_with_range_condition_works() {
+    let mut param_box = ParameterBox::new();
+
+    param_box.add::<f64>("x").unwrap();
+    param_box
+        .set_min_limit::<f64>("x", RangeCondition::Open(0.0))
+        .unwrap();
+    param_box
+        .set_max_limit::<f64>("x", RangeCondition::Close(1.0))
+        .unwrap();
+
+    param_box.set_value::<f64>("x", 1.0).unwrap();
+    assert!(param_box.set_value::<f64>("x", 0.0).is_err());
+    assert!(param_box.set_value::<f64>("x", 1.5).is_err());
+}
+
+#[test]
+fn read_file_accepts_crlf_line_endings() {
+    let filename = std::env::temp_dir().join("parameter_box_test_crlf.txt");
+    std::fs::write(&filename, "# comment\r\na 42\r\n\r\n  \r\nb 2.5\r\n").unwrap();
+
+    let mut param_box = ParameterBox::new();
+    param_box.add::<i32>("a").unwrap();
+    param_box.add::<f64>("b").unwrap();
+    param_box.read_file(filename.to_str().unwrap()).unwrap();
+    std::fs::remove_file(&filename).unwrap();
+
+    assert_eq!(param_box.clone_value::<i32>("a").unwrap().unwrap(), 42);
+    assert_eq!(param_box.clone_value::<f64>("b").unwrap().unwrap(), 2.5);
+}
+
+#[test]
+fn print_shows_whitelist_once() {
+    let mut param_box = ParameterBox::new();
+
+    param_box.add::<i32>("a").unwrap();
+    param_box.set_whitelist::<i32>("a", vec![1, 3]).unwrap();
+
+    let mut output = Vec::new();
+    param_box.print(&mut output).unwrap();
+    let output = String::from_utf8(output).unwrap();
+
+    assert_eq!(
+        output,
+        "a\n----------------------------\nType          | i32\nWhitelist     | [1, 3]\n\n"
+    );
+}
+
+#[test]
+fn print_xml_works() {
+    let mut param_box = ParameterBox::new();
+
+    param_box.add::<i32>("a").unwrap();
+    param_box.add::<String>("b").unwrap();
+    param_box.add::<f64>("hidden").unwrap();
+    param_box.set_value::<i32>("a", 3).unwrap();
+    param_box.set_range_open_close::<i32>("a", (0, 10)).unwrap();
+    param_box.set_whitelist::<i32>("a", vec![1, 3]).unwrap();
+    param_box
+        .set_explanation("b", "Tom & \"Jerry\" <cat>".to_string())
+        .unwrap();
+    param_box.set_invisible("hidden").unwrap();
+
+    let mut output = Vec::new();
+    param_box.print_xml(&mut output).unwrap();
+    let output = String::from_utf8(output).unwrap();
+
+    assert_eq!(
+        output,
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<parameters>
+  <parameter name="a" type="i32">
+    <value>3</value>
+    <range>
+      <min>0 &lt;</min>
+      <max>≦ 10</max>
+    </range>
+    <whitelist>[1, 3]</whitelist>
+  </parameter>
+  <parameter name="b" type="alloc::string::String">
+    <explanation>Tom &amp; &quot;Jerry&quot; &lt;cat&gt;</explanation>
+  </parameter>
+</parameters>
+"#
+    );
+}
+
+#[test]
+fn get_all_values_as_strings_works() {
+    let mut param_box = ParameterBox::new();
+
+    param_box.add::<i32>("b").unwrap();
+    param_box.add::<String>("a").unwrap();
+    param_box.add::<f64>("c").unwrap();
+    param_box.set_value::<i32>("b", -1).unwrap();
+    param_box.set_value::<f64>("c", 0.5).unwrap();
+
+    assert_eq!(
+        param_box.get_all_values_as_strings(),
+        vec![
+            ("b".to_string(), Some("-1".to_string())),
+            ("a".to_string(), None),
+            ("c".to_string(), Some("0.5".to_string())),
+        ]
+    );
+}
+
+#[test]
+fn copy_constraints_from_works() {
+    let mut param_box = ParameterBox::new();
+
+    param_box.add::<i32>("source").unwrap();
+    param_box.add::<i32>("dest").unwrap();
+    param_box.add::<f64>("other").unwrap();
+    param_box
+        .set_range_close_close::<i32>("source", (0, 10))
+        .unwrap();
+    param_box.set_blacklist::<i32>("source", vec![5]).unwrap();
+    param_box.set_value::<i32>("dest", 5).unwrap();
+
+    // The value of `dest` violates the copied blacklist, but the constraints are still copied.
+    assert!(param_box.copy_constraints_from("source", "dest").is_err());
+    assert!(param_box.set_value::<i32>("dest", 11).is_err());
+    param_box.set_value::<i32>("dest", 3).unwrap();
+
+    assert!(param_box.copy_constraints_from("source", "other").is_err());
+    assert!(param_box.copy_constraints_from("source", "none").is_err());
+}
+
+#[test]
+fn copy_constraints_from_copies_all_constraints() {
+    let mut param_box = ParameterBox::new();
+    param_box.add::<String>("s").unwrap();
+    param_box.add::<String>("t").unwrap();
+    param_box.set_max_length("s", 2).unwrap();
+    param_box.copy_constraints_from("s", "t").unwrap();
+    assert!(param_box.set_value("t", "long".to_string()).is_err());
+    param_box.set_value("t", "ok".to_string()).unwrap();
+
+    param_box.add::<u32>("even").unwrap();
+    param_box.add::<u32>("n").unwrap();
+    param_box
+        .set_validator("even", |n: &u32| {
+            if n.is_multiple_of(2) {
+                Ok(())
+            } else {
+                Err(format!("{} is odd.", n))
+            }
+        })
+        .unwrap();
+    param_box
+        .set_warning_range_close_close("even", (0_u32, 10))
+        .unwrap();
+    param_box.set_value::<u32>("n", 3).unwrap();
+    // The value of `n` is rejected by the copied validator, but the constraints are still copied.
+    assert!(param_box.copy_constraints_from("even", "n").is_err());
+    assert!(param_box.set_value::<u32>("n", 5).is_err());
+    param_box.set_value::<u32>("n", 12).unwrap();
+    assert_eq!(param_box.get_warnings().len(), 1);
+}
+
+#[test]
+fn set_list_works() {
+    let mut param_box = ParameterBox::new();
+
+    param_box.add::<u8>("p").unwrap();
+    for use_whitelist in [true, false].iter() {
+        let condition = if *use_whitelist {
+            ListCondition::White(vec![1, 2])
+        } else {
+            ListCondition::Black(vec![1, 2])
+        };
+        param_box.set_list::<u8>("p", condition).unwrap();
+        assert_eq!(param_box.set_value::<u8>("p", 1).is_ok(), *use_whitelist);
+        assert_eq!(param_box.set_value::<u8>("p", 3).is_ok(), !*use_whitelist);
+    }
+}
+
+#[test]
+fn print_only_invisible_works() {
+    let mut param_box = ParameterBox::new();
+
+    param_box.add::<i32>("shown").unwrap();
+    param_box.add::<String>("password").unwrap();
+    param_box.set_invisible("password").unwrap();
+
+    let mut output = Vec::new();
+    param_box.print_only_invisible(&mut output).unwrap();
+    let output = String::from_utf8(output).unwrap();
+
+    assert!(output.contains("password"));
+    assert!(!output.contains("shown"));
+}
+
+#[test]
+fn get_range_display_strings_works() {
+    let mut param_box = ParameterBox::new();
+
+    param_box.add::<i32>("a").unwrap();
+    assert_eq!(
+        param_box.get_range_display_strings("a").unwrap(),
+        (None, None)
+    );
+    param_box.set_range_open_close::<i32>("a", (0, 5)).unwrap();
+    assert_eq!(
+        param_box.get_range_display_strings("a").unwrap(),
+        (Some("0 <"), Some("≦ 5"))
+    );
+    assert!(param_box.get_range_display_strings("b").is_err());
+}
+
+#[test]
+fn read_file_reports_unparsable_value() {
+    let filename = std::env::temp_dir().join("parameter_box_test_unparsable.txt");
+    std::fs::write(&filename, "a abc\n").unwrap();
+
+    let mut param_box = ParameterBox::new();
+    param_box.add::<i32>("a").unwrap();
+    let result = param_box.read_file(filename.to_str().unwrap());
+    std::fs::remove_file(&filename).unwrap();
+
+    assert!(result.is_err());
+    assert_eq!(param_box.clone_value::<i32>("a").unwrap(), None);
+}
+
+#[test]
+fn load_from_sorted_pairs_works() {
+    let mut param_box = ParameterBox::new();
+
+    param_box.add::<f64>("beta").unwrap();
+    param_box.add::<i32>("alpha").unwrap();
+    param_box
+        .load_from_sorted_pairs(vec![
+            ("alpha".to_string(), "3".to_string()),
+            ("beta".to_string(), "0.25".to_string()),
+        ])
+        .unwrap();
+    assert_eq!(param_box.clone_value::<i32>("alpha").unwrap().unwrap(), 3);
+    assert_eq!(param_box.clone_value::<f64>("beta").unwrap().unwrap(), 0.25);
+
+    assert!(param_box
+        .load_from_sorted_pairs(vec![
+            ("beta".to_string(), "0.5".to_string()),
+            ("alpha".to_string(), "1".to_string()),
+        ])
+        .is_err());
+    assert!(param_box
+        .load_from_sorted_pairs(vec![("gamma".to_string(), "1".to_string())])
+        .is_err());
+
+    let num_errors = param_box.get_num_errors();
+    assert!(param_box
+        .load_from_sorted_pairs(vec![
+            ("alpha".to_string(), "5".to_string()),
+            ("alpha".to_string(), "6".to_string()),
+            ("beta".to_string(), "0.75".to_string()),
+        ])
+        .is_err());
+    assert_eq!(param_box.get_num_errors(), num_errors + 1);
+    assert_eq!(param_box.clone_value::<i32>("alpha").unwrap().unwrap(), 5);
+    assert_eq!(param_box.clone_value::<f64>("beta").unwrap().unwrap(), 0.75);
+}
+
+#[test]
+fn iter_names_and_types_works() {
+    let mut param_box = ParameterBox::new();
+
+    param_box.add::<u8>("z").unwrap();
+    param_box.add::<bool>("a").unwrap();
+
+    assert_eq!(
+        param_box.iter_names_and_types().collect::<Vec<_>>(),
+        vec![("z", "u8"), ("a", "bool")]
+    );
+}
+
+#[test]
+fn get_total_size_estimate_grows_with_parameters() {
+    let mut param_box = ParameterBox::new();
+
+    let empty_size = param_box.get_total_size_estimate();
+    param_box.add::<f64>("x").unwrap();
+    let one_size = param_box.get_total_size_estimate();
+    param_box
+        .set_explanation("x", "A long explanation.".to_string())
+        .unwrap();
+
+    assert!(empty_size < one_size);
+    assert!(one_size < param_box.get_total_size_estimate());
+}
+
+#[test]
+fn print_value_only_works() {
+    let mut param_box = ParameterBox::new();
+
+    param_box.add::<f64>("lr").unwrap();
+    param_box.add::<u32>("epochs").unwrap();
+    param_box.set_value::<f64>("lr", 0.01).unwrap();
+
+    let mut output = Vec::new();
+    param_box.print_value_only(&mut output, "lr").unwrap();
+    param_box.print_value_only(&mut output, "epochs").unwrap();
+    assert_eq!(
+        String::from_utf8(output).unwrap(),
+        "lr = 0.01\nepochs = (unset)\n"
+    );
+    assert!(param_box
+        .print_value_only(&mut Vec::new(), "batch")
+        .is_err());
+}
+
+#[test]
+fn try_set_range_close_close_works() {
+    let mut param_box = ParameterBox::new();
+
+    param_box.add::<i32>("unset").unwrap();
+    param_box.add::<i32>("set").unwrap();
+    param_box.set_value::<i32>("set", 100).unwrap();
+
+    assert!(param_box
+        .try_set_range_close_close::<i32>("unset", (0, 10))
+        .unwrap());
+    assert!(!param_box
+        .try_set_range_close_close::<i32>("set", (0, 10))
+        .unwrap());
+    assert_eq!(
+        param_box.get_range_display_strings("set").unwrap(),
+        (None, None)
+    );
+    assert!(param_box.set_value::<i32>("unset", 11).is_err());
+    assert!(param_box
+        .try_set_range_close_close::<i32>("none", (0, 10))
+        .is_err());
+}
+
+#[test]
+fn add_from_type_erased_works() {
+    let mut param_box = ParameterBox::new();
+
+    param_box
+        .add_from_type_erased(TypeErasedParam {
+            name: "alpha".to_string(),
+            type_name: "f64".to_string(),
+            default_value: Some("0.5".to_string()),
+            explanation: Some("Mixing ratio".to_string()),
+        })
+        .unwrap();
+    param_box
+        .add_from_type_erased(TypeErasedParam {
+            name: "label".to_string(),
+            type_name: "alloc::string::String".to_string(),
+            default_value: None,
+            explanation: None,
+        })
+        .unwrap();
+    assert!(param_box
+        .add_from_type_erased(TypeErasedParam {
+            name: "matrix".to_string(),
+            type_name: "Matrix".to_string(),
+            default_value: None,
+            explanation: None,
+        })
+        .is_err());
+
+    assert_eq!(param_box.clone_value::<f64>("alpha").unwrap().unwrap(), 0.5);
+    assert_eq!(param_box.clone_value::<String>("label").unwrap(), None);
+    assert_eq!(
+        param_box.ref_explanation("alpha").unwrap().as_deref(),
+        Some("Mixing ratio")
+    );
+}
+
+#[test]
+fn contains_all_and_contains_any_work() {
+    let mut param_box = ParameterBox::new();
+
+    param_box.add::<f64>("alpha").unwrap();
+    param_box.add::<f64>("beta").unwrap();
+
+    assert!(param_box.contains_all(&["alpha", "beta"]));
+    assert!(!param_box.contains_all(&["alpha", "gamma"]));
+    assert!(param_box.contains_any(&["gamma", "beta"]));
+    assert!(!param_box.contains_any(&["gamma"]));
+    assert_eq!(param_box.get_num_errors(), 0);
+}
+
+#[test]
+fn print_unset_warnings_works() {
+    let mut param_box = ParameterBox::new();
+
+    param_box.add::<f64>("set").unwrap();
+    param_box.add::<u32>("unset").unwrap();
+    param_box.set_value::<f64>("set", 1.0).unwrap();
+
+    let mut output = Vec::new();
+    param_box.print_unset_warnings(&mut output).unwrap();
+    assert_eq!(
+        String::from_utf8(output).unwrap(),
+        "WARNING: parameter `unset` (u32) has no value set\n"
+    );
+}
+
+#[test]
+fn clone_all_values_works() {
+    let mut param_box = ParameterBox::new();
+
+    param_box.add::<f64>("x").unwrap();
+    param_box.add::<i32>("n").unwrap();
+    param_box.add::<f64>("y").unwrap();
+    param_box.set_value::<f64>("y", 2.0).unwrap();
+
+    assert_eq!(
+        param_box.clone_all_values::<f64>(),
+        vec![("x".to_string(), None), ("y".to_string(), Some(2.0))]
+    );
+    assert!(param_box.clone_all_values::<String>().is_empty());
+}
+
+#[test]
+fn make_required_all_works() {
+    let mut param_box = ParameterBox::new();
+
+    param_box.add::<f64>("x").unwrap();
+    param_box.add::<f64>("y").unwrap();
+    param_box.set_value::<f64>("x", 1.0).unwrap();
+    param_box.validate_required().unwrap();
+
+    param_box.make_required_all();
+    assert!(param_box.validate_required().is_err());
+    param_box.set_value::<f64>("y", 2.0).unwrap();
+    param_box.validate_required().unwrap();
+
+    param_box.add::<f64>("z").unwrap();
+    param_box.validate_required().unwrap();
+    param_box.make_required_all();
+    assert!(param_box.validate_required().is_err());
+    param_box.make_optional_all();
+    param_box.validate_required().unwrap();
+}
+
+#[test]
+fn read_file_reports_unsupported_type() {
+    #[derive(Clone, PartialEq, PartialOrd)]
+    struct Point(i32, i32);
+    impl std::fmt::Display for Point {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "({}, {})", self.0, self.1)
+        }
+    }
+
+    let filename = std::env::temp_dir().join("parameter_box_test_unsupported.txt");
+    std::fs::write(&filename, "origin 0,0\n").unwrap();
+
+    let mut param_box = ParameterBox::new();
+    param_box.add::<Point>("origin").unwrap();
+    let result = param_box.read_file(filename.to_str().unwrap());
+    std::fs::remove_file(&filename).unwrap();
+
+    match result {
+        Err(ParameterBoxError::InvalidInputFile(err_msg)) => {
+            assert!(err_msg.contains("`set_value`"));
+            assert!(err_msg.contains("1-th line"));
+        }
+        _ => panic!("unsupported type must be reported"),
+    }
+    assert_eq!(param_box.get_num_errors(), 1);
+}
+
+#[test]
+fn get_list_display_string_works() {
+    let mut param_box = ParameterBox::new();
+
+    param_box.add::<i32>("a").unwrap();
+    assert_eq!(param_box.get_list_display_string("a").unwrap(), None);
+    param_box.set_whitelist::<i32>("a", vec![1, 2]).unwrap();
+    assert_eq!(
+        param_box.get_list_display_string("a").unwrap(),
+        Some(("Whitelist", "[1, 2]"))
+    );
+    assert!(param_box.get_list_display_string("b").is_err());
+}
+
+#[test]
+fn set_range_from_str_works() {
+    let mut param_box = ParameterBox::new();
+
+    param_box.add::<f64>("alpha").unwrap();
+    param_box.add::<i32>("n").unwrap();
+    param_box
+        .set_range_from_str::<f64>("alpha", "0.0", "1.0", true)
+        .unwrap();
+    param_box
+        .set_range_from_str::<i32>("n", "0", "10", false)
+        .unwrap();
+
+    assert!(param_box.set_value::<f64>("alpha", 1.0).is_err());
+    param_box.set_value::<i32>("n", 10).unwrap();
+    assert!(param_box
+        .set_range_from_str::<i32>("n", "zero", "10", false)
+        .is_err());
+    match param_box.set_range_from_str::<i32>("n", "zero", "ten", false) {
+        Err(ParameterBoxError::InvalidParse(err_msg)) => {
+            assert_eq!(err_msg.lines().count(), 2);
+        }
+        _ => panic!("both limits must be reported"),
+    }
+}
+
+#[test]
+fn print_nondefault_works() {
+    let mut param_box = ParameterBox::new();
+
+    param_box.add_with_default::<f64>("kept", 0.5).unwrap();
+    param_box.add_with_default::<f64>("changed", 0.5).unwrap();
+    param_box.add::<i32>("no_default").unwrap();
+    param_box.add::<i32>("unset").unwrap();
+    param_box.set_value::<f64>("changed", 0.25).unwrap();
+    param_box.set_value::<i32>("no_default", 1).unwrap();
+
+    let mut output = Vec::new();
+    param_box.print_nondefault(&mut output).unwrap();
+    let output = String::from_utf8(output).unwrap();
+
+    assert!(!output.contains("kept"));
+    assert!(output.contains("changed"));
+    assert!(output.contains("no_default"));
+    assert!(!output.contains("unset"));
+}
+
+#[test]
+fn move_to_front_and_back_work() {
+    let mut param_box = ParameterBox::new();
+
+    param_box.add::<i32>("a").unwrap();
+    param_box.add::<i32>("b").unwrap();
+    param_box.add::<i32>("c").unwrap();
+    param_box.move_to_front("c").unwrap();
+    param_box.move_to_back("a").unwrap();
+
+    assert_eq!(
+        param_box
+            .iter_names_and_types()
+            .map(|(name, _)| name)
+            .collect::<Vec<_>>(),
+        vec!["c", "b", "a"]
+    );
+    assert!(param_box.move_to_front("d").is_err());
+}
+
+#[test]
+fn get_explanation_works() {
+    let mut param_box = ParameterBox::new();
+
+    param_box.add::<f64>("lr").unwrap();
+    param_box.add::<u32>("epochs").unwrap();
+    param_box
+        .set_explanation("lr", "Learning rate".to_string())
+        .unwrap();
+
+    assert_eq!(param_box.get_explanation("lr"), Some("Learning rate"));
+    assert_eq!(param_box.get_explanation("epochs"), None);
+    assert_eq!(param_box.get_explanation("none"), None);
+    assert_eq!(param_box.get_explanation_or_empty("lr"), "Learning rate");
+    assert_eq!(param_box.get_explanation_or_empty("epochs"), "");
+    assert_eq!(param_box.get_num_errors(), 0);
+}
+
+#[test]
+fn with_name_prefix_works() {
+    let mut param_box = ParameterBox::new();
+
+    param_box.add::<f64>("dt").unwrap();
+    param_box.add::<u32>("max_iter").unwrap();
+    param_box.set_value::<f64>("dt", 0.1).unwrap();
+    param_box
+        .set_range_open_open::<f64>("dt", (0.0, 1.0))
+        .unwrap();
+
+    let mut physics = param_box.with_name_prefix("physics");
+    assert_eq!(
+        physics.iter_names_and_types().collect::<Vec<_>>(),
+        vec![("physics.dt", "f64"), ("physics.max_iter", "u32")]
+    );
+    assert_eq!(
+        physics.clone_value::<f64>("physics.dt").unwrap().unwrap(),
+        0.1
+    );
+    assert!(physics.set_value::<f64>("physics.dt", 2.0).is_err());
+
+    // The original parameter box is not modified.
+    physics.set_value::<f64>("physics.dt", 0.5).unwrap();
+    assert_eq!(param_box.clone_value::<f64>("dt").unwrap().unwrap(), 0.1);
+}
+
+#[test]
+fn with_name_prefix_keeps_cross_constraints() {
+    let mut param_box = ParameterBox::new();
+    param_box.add::<u32>("min").unwrap();
+    param_box.add::<u32>("max").unwrap();
+    param_box
+        .add_cross_constraint("min <= max", &["min", "max"], |param_box| {
+            let min = param_box.clone_value::<u32>("min").unwrap();
+            let max = param_box.clone_value::<u32>("max").unwrap();
+            match (min, max) {
+                (Some(min), Some(max)) if min > max => Err(format!("{} > {}", min, max)),
+                _ => Ok(()),
+            }
+        })
+        .unwrap();
+
+    let mut prefixed = param_box.with_name_prefix("range");
+    prefixed.set_value::<u32>("range.min", 1).unwrap();
+    prefixed.set_value::<u32>("range.max", 2).unwrap();
+    prefixed.validate_cross_constraints().unwrap();
+    prefixed.set_value::<u32>("range.min", 3).unwrap();
+    match prefixed.validate_cross_constraints() {
+        Err(ParameterBoxError::InvalidCondition(err_msg)) => {
+            assert!(err_msg.contains("`range.min`, `range.max`"));
+            assert!(err_msg.contains("3 > 2"));
+        }
+        _ => panic!("the cross constraint must be carried over"),
+    }
+}
+
+#[test]
+fn write_file_round_trips() {
+    let filename = std::env::temp_dir().join("parameter_box_test_write_file.txt");
+    let filename = filename.to_str().unwrap();
+
+    let mut param_box = ParameterBox::new();
+    param_box.add::<f64>("lr").unwrap();
+    param_box.add::<u32>("epochs").unwrap();
+    param_box.add::<String>("unset").unwrap();
+    param_box.set_value::<f64>("lr", 0.01).unwrap();
+    param_box.set_value::<u32>("epochs", 10).unwrap();
+    param_box
+        .set_explanation("lr", "Learning rate\nof the optimizer".to_string())
+        .unwrap();
+    param_box.set_invisible("epochs").unwrap();
+    param_box.write_file(filename).unwrap();
+
+    let content = std::fs::read_to_string(filename).unwrap();
+    assert_eq!(
+        content,
+        "# Learning rate\n# of the optimizer\nlr 0.01\nepochs 10\n"
+    );
+
+    let mut read_box = ParameterBox::new();
+    read_box.add::<f64>("lr").unwrap();
+    read_box.add::<u32>("epochs").unwrap();
+    read_box.add::<String>("unset").unwrap();
+    read_box.read_file(filename).unwrap();
+    std::fs::remove_file(filename).unwrap();
+
+    assert_eq!(read_box.clone_value::<f64>("lr").unwrap().unwrap(), 0.01);
+    assert_eq!(read_box.clone_value::<u32>("epochs").unwrap().unwrap(), 10);
+    assert_eq!(read_box.clone_value::<String>("unset").unwrap(), None);
+}
+
+#[test]
+fn read_from_str_works() {
+    let mut param_box = ParameterBox::new();
+
+    param_box.add::<i32>("a").unwrap();
+    param_box.add::<String>("b").unwrap();
+    param_box
+        .read_from_str("# comment\r\na 42\r\n\r\nb apple\r\n")
+        .unwrap();
+    assert_eq!(param_box.clone_value::<i32>("a").unwrap().unwrap(), 42);
+    assert_eq!(
+        param_box.clone_value::<String>("b").unwrap().unwrap(),
+        "apple"
+    );
+
+    match param_box.read_from_str("a 1\na 2\n") {
+        Err(ParameterBoxError::InvalidInputFile(err_msg)) => {
+            assert!(err_msg.contains("'<string>'"));
+            assert!(err_msg.contains("duplicate"));
+        }
+        _ => panic!("duplicate parameters must be reported"),
+    }
+}
+
+#[test]
+fn parameter_box_error_implements_error() {
+    fn read_missing_file() -> Result<(), Box<dyn std::error::Error>> {
+        let mut param_box = ParameterBox::new();
+        param_box.read_file("/nonexistent/parameter_box_test.txt")?;
+        Ok(())
+    }
+
+    let err = read_missing_file().unwrap_err();
+    assert!(err.to_string().starts_with("ParameterBoxError"));
+    let io_error = err
+        .source()
+        .unwrap()
+        .downcast_ref::<std::io::Error>()
+        .unwrap();
+    assert_eq!(io_error.kind(), std::io::ErrorKind::NotFound);
+
+    let mut param_box = ParameterBox::new();
+    let err = param_box.set_invisible("none").unwrap_err();
+    assert!(err.to_string().contains("`none` has not been added"));
+    assert!(std::error::Error::source(&err).is_none());
+}
+
+#[test]
+fn parameter_box_builder_works() {
+    let mut param_box = ParameterBoxBuilder::new()
+        .add::<f64>("lr")
+        .value(0.001)
+        .range_open_open(0.0, 1.0)
+        .explanation("learning rate")
+        .done()
+        .add::<u32>("epochs")
+        .value(100)
+        .whitelist(vec![10, 100])
+        .invisible()
+        .done()
+        .build()
+        .unwrap();
+    assert_eq!(param_box.clone_value::<f64>("lr").unwrap().unwrap(), 0.001);
+    assert_eq!(param_box.get_explanation("lr"), Some("learning rate"));
+    assert!(param_box.set_value::<u32>("epochs", 50).is_err());
+
+    let result = ParameterBoxBuilder::new()
+        .add::<f64>("lr")
+        .value(2.0)
+        .range_close_close(0.0, 1.0)
+        .done()
+        .add::<i32>("lr")
+        .done()
+        .build();
+    match result {
+        Err(ParameterBoxError::InvalidCondition(err_msg)) => {
+            assert_eq!(err_msg.lines().count(), 2);
+        }
+        _ => panic!("errors must be combined"),
+    }
+}
+
+#[test]
+fn print_json_is_valid_json() {
+    let mut param_box = ParameterBox::new();
+    param_box.add::<f64>("lr").unwrap();
+    param_box.set_value("lr", 0.5).unwrap();
+    param_box.set_range_open_close("lr", (0.0, 1.0)).unwrap();
+    param_box
+        .set_explanation("lr", "learning \"rate\"".to_string())
+        .unwrap();
+    param_box.add::<String>("mode").unwrap();
+    param_box
+        .set_whitelist("mode", vec!["a".to_string(), "b".to_string()])
+        .unwrap();
+    param_box.set_invisible("mode").unwrap();
+    let mut output = Vec::new();
+    param_box.print_json(&mut output).unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&output).unwrap();
+    assert_eq!(json["lr"]["type"], "f64");
+    assert_eq!(json["lr"]["value"], "0.5");
+    assert_eq!(json["lr"]["explanation"], "learning \"rate\"");
+    assert_eq!(json["lr"]["list_type"], serde_json::Value::Null);
+    assert_eq!(json["lr"]["visible"], true);
+    assert_eq!(json["mode"]["value"], serde_json::Value::Null);
+    assert_eq!(json["mode"]["list_type"], "whitelist");
+    assert_eq!(json["mode"]["list_values"], serde_json::json!(["a", "b"]));
+    assert_eq!(json["lr"]["list_values"], serde_json::Value::Null);
+    assert_eq!(json["mode"]["visible"], false);
+
+    let mut output = Vec::new();
+    ParameterBox::new().print_json(&mut output).unwrap();
+    assert!(serde_json::from_slice::<serde_json::Value>(&output).is_ok());
+}
+
+#[test]
+fn read_env_vars_with_prefix_works() {
+    let mut param_box = ParameterBox::new();
+    param_box.add::<f64>("learning_rate").unwrap();
+    param_box.add::<u32>("epochs").unwrap();
+    param_box.add::<String>("label").unwrap();
+    param_box.set_value::<u32>("epochs", 10).unwrap();
+    param_box
+        .set_range_close_close::<f64>("learning_rate", (0.0, 1.0))
+        .unwrap();
+    std::env::set_var("PBTESTENV_LEARNING_RATE", "0.001");
+    std::env::set_var("PBTESTENV_LABEL", "it's a run");
+    param_box.read_env_vars_with_prefix("PBTESTENV").unwrap();
+    assert_eq!(
+        param_box
+            .clone_value::<f64>("learning_rate")
+            .unwrap()
+            .unwrap(),
+        0.001
+    );
+    assert_eq!(param_box.clone_value::<u32>("epochs").unwrap().unwrap(), 10);
+    assert_eq!(
+        param_box.to_shell_exports("APP"),
+        "export APP_LEARNING_RATE=0.001\nexport APP_EPOCHS=10\nexport APP_LABEL='it'\\''s a run'\n"
+    );
+
+    std::env::set_var("PBTESTENV_LEARNING_RATE", "abc");
+    assert!(matches!(
+        param_box.read_env_vars_with_prefix("PBTESTENV"),
+        Err(ParameterBoxError::InvalidParse(_))
+    ));
+    std::env::set_var("PBTESTENV_LEARNING_RATE", "2.0");
+    assert!(matches!(
+        param_box.read_env_vars_with_prefix("PBTESTENV"),
+        Err(ParameterBoxError::InvalidCondition(_))
+    ));
+    std::env::remove_var("PBTESTENV_LEARNING_RATE");
+    std::env::remove_var("PBTESTENV_LABEL");
+}
+
+#[test]
+fn env_var_names_are_shell_identifiers() {
+    let mut param_box = ParameterBox::new();
+    param_box.add::<f64>("dt").unwrap();
+    param_box.add::<u32>("max-iter").unwrap();
+    let mut physics = param_box.with_name_prefix("physics");
+    physics.set_value::<f64>("physics.dt", 0.1).unwrap();
+    physics.set_value::<u32>("physics.max-iter", 5).unwrap();
+    assert_eq!(
+        physics.to_shell_exports("APP"),
+        "export APP_PHYSICS_DT=0.1\nexport APP_PHYSICS_MAX_ITER=5\n"
+    );
+
+    let mut read_box = param_box.with_name_prefix("physics");
+    std::env::set_var("PBTESTDOTTED_PHYSICS_DT", "0.25");
+    std::env::set_var("PBTESTDOTTED_PHYSICS_MAX_ITER", "7");
+    read_box.read_env_vars_with_prefix("PBTESTDOTTED").unwrap();
+    std::env::remove_var("PBTESTDOTTED_PHYSICS_DT");
+    std::env::remove_var("PBTESTDOTTED_PHYSICS_MAX_ITER");
+    assert_eq!(
+        read_box.clone_value::<f64>("physics.dt").unwrap().unwrap(),
+        0.25
+    );
+    assert_eq!(
+        read_box
+            .clone_value::<u32>("physics.max-iter")
+            .unwrap()
+            .unwrap(),
+        7
+    );
+}
+
+#[test]
+fn read_cli_args_works() {
+    let args = |args: &[&str]| args.iter().map(|x| x.to_string()).collect::<Vec<String>>();
+    let mut param_box = ParameterBox::new();
+    param_box.add::<f64>("lr").unwrap();
+    param_box.add::<u32>("epochs").unwrap();
+    param_box.add::<bool>("verbose").unwrap();
+    param_box.add::<bool>("cache").unwrap();
+    param_box
+        .read_cli_args(&args(&[
+            "--lr",
+            "0.5",
+            "--epochs=20",
+            "--verbose",
+            "--no-cache",
+        ]))
+        .unwrap();
+    assert_eq!(param_box.clone_value::<f64>("lr").unwrap().unwrap(), 0.5);
+    assert_eq!(param_box.clone_value::<u32>("epochs").unwrap().unwrap(), 20);
+    assert!(param_box.clone_value::<bool>("verbose").unwrap().unwrap());
+    assert!(!param_box.clone_value::<bool>("cache").unwrap().unwrap());
+
+    assert!(matches!(
+        param_box.read_cli_args(&args(&["--unknown", "1"])),
+        Err(ParameterBoxError::NotAdded(_))
+    ));
+    assert!(matches!(
+        param_box.read_cli_args(&args(&["--epochs"])),
+        Err(ParameterBoxError::InvalidParse(_))
+    ));
+    param_box.set_lenient_cli_args(true);
+    param_box
+        .read_cli_args(&args(&["--unknown", "1", "--epochs", "30"]))
+        .unwrap();
+    assert_eq!(param_box.clone_value::<u32>("epochs").unwrap().unwrap(), 30);
+    assert!(matches!(
+        param_box.read_cli_args(&args(&["--help"])),
+        Err(ParameterBoxError::HelpRequested(_))
+    ));
+}
+
+#[test]
+fn read_cli_args_reads_bool_values() {
+    let args = |args: &[&str]| args.iter().map(|x| x.to_string()).collect::<Vec<String>>();
+    let mut param_box = ParameterBox::new();
+    param_box.add::<bool>("verbose").unwrap();
+    param_box.add::<u32>("epochs").unwrap();
+    param_box
+        .read_cli_args(&args(&["--verbose", "false", "--epochs", "20"]))
+        .unwrap();
+    assert!(!param_box.clone_value::<bool>("verbose").unwrap().unwrap());
+    assert_eq!(param_box.clone_value::<u32>("epochs").unwrap().unwrap(), 20);
+
+    param_box
+        .read_cli_args(&args(&["--verbose", "--epochs", "30"]))
+        .unwrap();
+    assert!(param_box.clone_value::<bool>("verbose").unwrap().unwrap());
+    assert_eq!(param_box.clone_value::<u32>("epochs").unwrap().unwrap(), 30);
+
+    assert!(matches!(
+        param_box.read_cli_args(&args(&["--verbose", "maybe"])),
+        Err(ParameterBoxError::InvalidParse(_))
+    ));
+}
+
+#[test]
+fn validate_all_set_works() {
+    let mut param_box = ParameterBox::new();
+    param_box.add::<f64>("lr").unwrap();
+    param_box.add::<u32>("epochs").unwrap();
+    param_box.add::<String>("label").unwrap();
+    param_box.set_value::<f64>("lr", 0.5).unwrap();
+    assert!(param_box.validate_all_set().is_ok());
+    param_box.make_required_all();
+    match param_box.validate_all_set() {
+        Err(ParameterBoxError::InvalidInputFile(err_msg)) => {
+            assert!(err_msg.contains("`epochs`, `label`"));
+        }
+        _ => panic!("unset parameters must be reported"),
+    }
+    assert!(param_box.validate_set_except(&["epochs"]).is_err());
+    assert!(param_box.validate_set_except(&["epochs", "label"]).is_ok());
+    param_box.set_value::<u32>("epochs", 10).unwrap();
+    param_box
+        .set_value::<String>("label", "a".to_string())
+        .unwrap();
+    assert!(param_box.validate_all_set().is_ok());
+}
+
+#[test]
+fn mark_required_works() {
+    let mut param_box = ParameterBox::new();
+    param_box.add::<f64>("lr").unwrap();
+    param_box.add::<u32>("epochs").unwrap();
+    param_box.mark_required("epochs").unwrap();
+    assert!(param_box.mark_required("unknown").is_err());
+    match param_box.read_from_str("lr 0.5\n") {
+        Err(ParameterBoxError::InvalidInputFile(err_msg)) => {
+            assert!(err_msg.contains("`epochs`"));
+            assert!(!err_msg.contains("`lr`"));
+        }
+        _ => panic!("required parameters must be reported"),
+    }
+
+    let mut output = Vec::new();
+    param_box.print(&mut output).unwrap();
+    assert!(String::from_utf8(output)
+        .unwrap()
+        .contains("epochs [required]"));
+    let mut output = Vec::new();
+    param_box.print_json(&mut output).unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&output).unwrap();
+    assert_eq!(json["epochs"]["required"], true);
+    assert_eq!(json["lr"]["required"], false);
+
+    param_box.read_from_str("epochs 10\n").unwrap();
+    assert!(param_box.validate_all_set().is_ok());
+}
+
+#[test]
+fn set_validator_works() {
+    let mut param_box = ParameterBox::new();
+    param_box.add::<u32>("n").unwrap();
+    param_box.set_value::<u32>("n", 4).unwrap();
+    let is_prime = |n: &u32| {
+        if *n >= 2 && (2..*n).all(|d| !n.is_multiple_of(d)) {
+            Ok(())
+        } else {
+            Err(format!("{} is not prime.", n))
+        }
+    };
+    assert!(param_box.set_validator("n", is_prime).is_err());
+    param_box.set_value::<u32>("n", 7).unwrap();
+    match param_box.set_value::<u32>("n", 9) {
+        Err(ParameterBoxError::InvalidCondition(err_msg)) => {
+            assert!(err_msg.contains("9 is not prime."));
+        }
+        _ => panic!("the validator must reject 9"),
+    }
+    assert!(param_box.read_from_str("n 10\n").is_err());
+    param_box.read_from_str("n 11\n").unwrap();
+    param_box
+        .set_validator("n", |n: &u32| {
+            if n.is_multiple_of(2) {
+                Ok(())
+            } else {
+                Err("odd".to_string())
+            }
+        })
+        .unwrap_err();
+    param_box.set_value::<u32>("n", 10).unwrap();
+}
+
+#[test]
+fn add_cross_constraint_works() {
+    let mut param_box = ParameterBox::new();
+    param_box.add::<f64>("learning_rate").unwrap();
+    param_box.add::<u32>("batch_size").unwrap();
+    param_box
+        .add_cross_constraint(
+            "learning_rate × batch_size < 1",
+            &["learning_rate", "batch_size"],
+            |param_box| {
+                let learning_rate = param_box.clone_all_values::<f64>()[0].1;
+                let batch_size = param_box.clone_all_values::<u32>()[0].1;
+                match (learning_rate, batch_size) {
+                    (Some(lr), Some(bs)) if lr * f64::from(bs) >= 1.0 => {
+                        Err(format!("{} × {} is not less than 1.", lr, bs))
+                    }
+                    _ => Ok(()),
+                }
+            },
+        )
+        .unwrap();
+    assert!(param_box
+        .add_cross_constraint("unknown", &["unknown"], |_| Ok(()))
+        .is_err());
+    param_box.set_value("learning_rate", 0.1).unwrap();
+    param_box.set_value::<u32>("batch_size", 5).unwrap();
+    param_box.validate_cross_constraints().unwrap();
+    param_box.set_value::<u32>("batch_size", 20).unwrap();
+    match param_box.validate_cross_constraints() {
+        Err(ParameterBoxError::InvalidCondition(err_msg)) => {
+            assert!(err_msg.contains("`learning_rate`, `batch_size`"));
+        }
+        _ => panic!("the cross constraint must be violated"),
+    }
+    assert!(param_box.validate_all_set().is_ok());
+    param_box.set_full_validation(true);
+    assert!(param_box.validate_all_set().is_err());
+}
+
+#[test]
+fn contains_works() {
+    let mut param_box = ParameterBox::new();
+    param_box.add::<f64>("lr").unwrap();
+    assert!(param_box.contains("lr"));
+    assert!(!param_box.contains("epochs"));
+    assert_eq!(param_box.get_num_errors(), 0);
+}
+
+#[test]
+fn len_and_is_empty_work() {
+    let mut param_box = ParameterBox::new();
+    assert_eq!(param_box.len(), 0);
+    assert!(param_box.is_empty());
+    param_box.add::<f64>("lr").unwrap();
+    param_box.add::<u32>("epochs").unwrap();
+    assert_eq!(param_box.len(), 2);
+    assert!(!param_box.is_empty());
+}
+
+#[test]
+fn parameter_names_works() {
+    let mut param_box = ParameterBox::new();
+    param_box.add::<u32>("epochs").unwrap();
+    param_box.add::<f64>("lr").unwrap();
+    let param_box = std::rc::Rc::new(param_box);
+    assert_eq!(
+        param_box.parameter_names().collect::<Vec<&str>>(),
+        vec!["epochs", "lr"]
+    );
+}
+
+#[test]
+fn parameter_box_can_be_cloned() {
+    let mut param_box = ParameterBox::new();
+    param_box.add::<f64>("lr").unwrap();
+    param_box.set_range_close_close("lr", (0.0, 1.0)).unwrap();
+    param_box.set_value("lr", 0.5).unwrap();
+    let mut cloned_box = param_box.clone();
+    cloned_box.set_value("lr", 0.25).unwrap();
+    assert!(cloned_box.set_value("lr", 2.0).is_err());
+    assert_eq!(param_box.clone_value::<f64>("lr").unwrap().unwrap(), 0.5);
+    assert_eq!(param_box.get_num_errors(), 0);
+}
+
+#[test]
+fn remove_works() {
+    let mut param_box = ParameterBox::new();
+    param_box.add::<f64>("lr").unwrap();
+    param_box.add::<u32>("epochs").unwrap();
+    param_box
+        .add_cross_constraint("always violated", &["lr"], |_| Err("violated".to_string()))
+        .unwrap();
+    param_box.remove("lr").unwrap();
+    assert!(!param_box.contains("lr"));
+    assert_eq!(
+        param_box.parameter_names().collect::<Vec<_>>(),
+        vec!["epochs"]
+    );
+    param_box.validate_cross_constraints().unwrap();
+    assert_eq!(param_box.get_num_errors(), 0);
+    assert!(matches!(
+        param_box.remove("lr"),
+        Err(ParameterBoxError::NotAdded(_))
+    ));
+    param_box.add::<i32>("lr").unwrap();
+}
+
+#[test]
+fn rename_works() {
+    let mut param_box = ParameterBox::new();
+    param_box.add::<f64>("legacy_lr").unwrap();
+    param_box.add::<u32>("epochs").unwrap();
+    param_box
+        .set_range_close_close("legacy_lr", (0.0, 1.0))
+        .unwrap();
+    param_box.set_value("legacy_lr", 0.5).unwrap();
+    param_box
+        .set_explanation("legacy_lr", "learning rate".to_string())
+        .unwrap();
+    param_box.rename("legacy_lr", "learning_rate").unwrap();
+    assert_eq!(
+        param_box.parameter_names().collect::<Vec<_>>(),
+        vec!["learning_rate", "epochs"]
+    );
+    assert_eq!(
+        param_box
+            .clone_value::<f64>("learning_rate")
+            .unwrap()
+            .unwrap(),
+        0.5
+    );
+    assert_eq!(
+        param_box.get_explanation("learning_rate"),
+        Some("learning rate")
+    );
+    assert!(param_box.set_value("learning_rate", 2.0).is_err());
+    assert!(matches!(
+        param_box.rename("legacy_lr", "lr"),
+        Err(ParameterBoxError::NotAdded(_))
+    ));
+    assert!(matches!(
+        param_box.rename("learning_rate", "epochs"),
+        Err(ParameterBoxError::AlreadyAdded(_))
+    ));
+    assert!(param_box.read_from_str("legacy_lr 0.1\n").is_err());
+}
+
+#[test]
+fn rename_keeps_cross_constraints_working() {
+    let mut param_box = ParameterBox::new();
+    param_box.add::<u32>("a").unwrap();
+    param_box.add::<u32>("max").unwrap();
+    param_box
+        .add_cross_constraint("a <= max", &["a", "max"], |param_box| {
+            let a = param_box
+                .clone_value::<u32>("a")
+                .map_err(|err| err.to_string())?;
+            let max = param_box.clone_value::<u32>("max").unwrap();
+            match (a, max) {
+                (Some(a), Some(max)) if a > max => Err(format!("{} > {}", a, max)),
+                _ => Ok(()),
+            }
+        })
+        .unwrap();
+    param_box.set_value::<u32>("a", 1).unwrap();
+    param_box.set_value::<u32>("max", 2).unwrap();
+
+    param_box.rename("a", "b").unwrap();
+    param_box.validate_cross_constraints().unwrap();
+    param_box.rename("b", "c").unwrap();
+    param_box.validate_cross_constraints().unwrap();
+    param_box.set_value::<u32>("c", 3).unwrap();
+    match param_box.validate_cross_constraints() {
+        Err(ParameterBoxError::InvalidCondition(err_msg)) => {
+            assert!(err_msg.contains("`c`, `max`"));
+            assert!(err_msg.contains("3 > 2"));
+        }
+        _ => panic!("the renamed parameter must still be checked"),
+    }
+}
+
+#[test]
+fn set_visible_and_set_invisible_work() {
+    let mut param_box = ParameterBox::new();
+    param_box.add::<u32>("epochs").unwrap();
+    param_box.set_value::<u32>("epochs", 10).unwrap();
+    let printed = |param_box: &ParameterBox| {
+        let mut output = Vec::new();
+        param_box.print(&mut output).unwrap();
+        String::from_utf8(output).unwrap()
+    };
+    param_box.set_invisible("epochs").unwrap();
+    assert!(!printed(&param_box).contains("epochs"));
+    param_box.set_visible("epochs").unwrap();
+    assert!(printed(&param_box).contains("epochs"));
+    #[allow(deprecated)]
+    param_box.set_unvisible("epochs").unwrap();
+    assert!(!printed(&param_box).contains("epochs"));
+    assert!(param_box.set_visible("unknown").is_err());
+}
+
+#[test]
+fn is_set_works() {
+    let mut param_box = ParameterBox::new();
+    param_box.add::<u32>("epochs").unwrap();
+    assert!(!param_box.is_set("epochs").unwrap());
+    param_box.set_value::<u32>("epochs", 10).unwrap();
+    assert!(param_box.is_set("epochs").unwrap());
+    assert!(matches!(
+        param_box.is_set("unknown"),
+        Err(ParameterBoxError::NotAdded(_))
+    ));
+}
+
+#[test]
+fn clear_value_works() {
+    let mut param_box = ParameterBox::new();
+    param_box.add::<f64>("lr").unwrap();
+    param_box.add::<u32>("epochs").unwrap();
+    param_box.set_range_close_close("lr", (0.0, 1.0)).unwrap();
+    param_box.set_value("lr", 0.5).unwrap();
+    param_box.set_value::<u32>("epochs", 10).unwrap();
+    param_box.clear_value("lr").unwrap();
+    assert_eq!(param_box.clone_value::<f64>("lr").unwrap(), None);
+    assert!(!param_box.is_set("lr").unwrap());
+    assert!(param_box.set_value("lr", 2.0).is_err());
+    assert!(param_box.clear_value("unknown").is_err());
+    param_box.reset_all_values();
+    assert!(!param_box.is_set("lr").unwrap());
+    assert!(!param_box.is_set("epochs").unwrap());
+}
+
+#[test]
+fn add_with_value_works() {
+    let mut param_box = ParameterBox::new();
+    param_box.add_with_value::<u32>("epochs", 10).unwrap();
+    assert_eq!(param_box.clone_value::<u32>("epochs").unwrap().unwrap(), 10);
+    assert!(matches!(
+        param_box.add_with_value::<u32>("epochs", 20),
+        Err(ParameterBoxError::AlreadyAdded(_))
+    ));
+    assert_eq!(param_box.clone_value::<u32>("epochs").unwrap().unwrap(), 10);
+}
+
+#[test]
+fn clear_range_works() {
+    let mut param_box = ParameterBox::new();
+    param_box.add::<f64>("lr").unwrap();
+    param_box.set_range_close_close("lr", (0.0, 1.0)).unwrap();
+    param_box.clear_min_limit("lr").unwrap();
+    param_box.set_value("lr", -1.0).unwrap();
+    assert!(param_box.set_value("lr", 2.0).is_err());
+    assert_eq!(
+        param_box.get_range_display_strings("lr").unwrap(),
+        (None, Some("≦ 1"))
+    );
+    param_box
+        .set_range_close_close("lr", (0.0, 1.0))
+        .unwrap_err();
+    param_box.clear_range("lr").unwrap();
+    param_box.set_value("lr", 2.0).unwrap();
+    param_box.set_value("lr", -2.0).unwrap();
+    assert_eq!(
+        param_box.get_range_display_strings("lr").unwrap(),
+        (None, None)
+    );
+    assert!(param_box.clear_range("unknown").is_err());
+}
+
+#[test]
+fn clear_list_works() {
+    let mut param_box = ParameterBox::new();
+    param_box.add::<u32>("epochs").unwrap();
+    param_box
+        .set_whitelist::<u32>("epochs", vec![10, 100])
+        .unwrap();
+    assert!(param_box.set_value::<u32>("epochs", 50).is_err());
+    param_box.clear_list("epochs").unwrap();
+    param_box.set_value::<u32>("epochs", 50).unwrap();
+    assert_eq!(param_box.get_list_display_string("epochs").unwrap(), None);
+    assert!(param_box.clear_list("unknown").is_err());
+}
+
+#[test]
+fn set_min_length_and_set_max_length_work() {
+    let mut param_box = ParameterBox::new();
+    param_box.add::<String>("password").unwrap();
+    param_box.add::<u32>("epochs").unwrap();
+    param_box.set_min_length("password", 8).unwrap();
+    param_box.set_max_length("password", 12).unwrap();
+    assert!(param_box
+        .set_value("password", "short".to_string())
+        .is_err());
+    assert!(param_box
+        .set_value("password", "much too long value".to_string())
+        .is_err());
+    param_box
+        .set_value("password", "just right".to_string())
+        .unwrap();
+    assert!(param_box.read_from_str("password abc\n").is_err());
+    assert!(param_box.set_min_length("epochs", 1).is_err());
+
+    let mut output = Vec::new();
+    param_box.print(&mut output).unwrap();
+    assert!(String::from_utf8(output)
+        .unwrap()
+        .contains("Length        | 8..12"));
+}
+
+#[test]
+fn set_nonzero_works() {
+    let mut param_box = ParameterBox::new();
+    param_box.add::<f64>("scale").unwrap();
+    param_box.add::<i8>("divisor").unwrap();
+    param_box.set_nonzero::<f64>("scale").unwrap();
+    assert!(param_box.set_value("scale", 0.0).is_err());
+    param_box.set_value("scale", 0.5).unwrap();
+    assert!(param_box.read_from_str("scale 0\n").is_err());
+    param_box.set_value::<i8>("divisor", 0).unwrap();
+    assert!(param_box.set_nonzero::<i8>("divisor").is_err());
+    assert!(param_box.set_nonzero::<i8>("unknown").is_err());
+
+    param_box.set_value("scale", 2.0).unwrap();
+    let mut output = Vec::new();
+    param_box.print(&mut output).unwrap();
+    assert!(String::from_utf8(output)
+        .unwrap()
+        .contains("Constraint    | ≠ 0"));
+}
+
+#[test]
+fn sign_constraints_work() {
+    let mut param_box = ParameterBox::new();
+    param_box.add::<f64>("weight").unwrap();
+    param_box.add::<i32>("offset").unwrap();
+    param_box.set_positive::<f64>("weight").unwrap();
+    assert!(param_box.set_value("weight", 0.0).is_err());
+    param_box.set_value("weight", 0.5).unwrap();
+    param_box.set_nonnegative::<f64>("weight").unwrap();
+    param_box.set_value("weight", 0.0).unwrap();
+
+    param_box.set_negative::<i32>("offset").unwrap();
+    assert!(param_box.set_value::<i32>("offset", 0).is_err());
+    param_box.set_value::<i32>("offset", -1).unwrap();
+    param_box.set_nonpositive::<i32>("offset").unwrap();
+    param_box.set_value::<i32>("offset", 0).unwrap();
+    assert!(param_box.set_value::<i32>("offset", 1).is_err());
+}
+
+#[test]
+fn set_finite_only_works() {
+    let mut param_box = ParameterBox::new();
+    param_box.add::<f64>("mass").unwrap();
+    param_box.add::<f32>("charge").unwrap();
+    param_box.set_finite_only::<f64>("mass").unwrap();
+    assert!(param_box.set_value("mass", f64::NAN).is_err());
+    assert!(param_box.set_value("mass", f64::INFINITY).is_err());
+    param_box.set_value("mass", 1.5).unwrap();
+    assert!(param_box.read_from_str("mass nan\n").is_err());
+    assert!(param_box.read_from_str("mass inf\n").is_err());
+    param_box.read_from_str("mass 2.5\n").unwrap();
+    param_box
+        .set_value::<f32>("charge", f32::NEG_INFINITY)
+        .unwrap();
+    assert!(param_box.set_finite_only::<f32>("charge").is_err());
+
+    let mut output = Vec::new();
+    param_box.print(&mut output).unwrap();
+    assert!(String::from_utf8(output)
+        .unwrap()
+        .contains("Constraint    | finite"));
+}
+
+#[test]
+fn iter_works() {
+    let mut param_box = ParameterBox::new();
+    param_box.add::<f64>("lr").unwrap();
+    param_box.add::<u32>("epochs").unwrap();
+    param_box.set_value("lr", 0.5).unwrap();
+    param_box.set_range_open_open("lr", (0.0, 1.0)).unwrap();
+    param_box.set_invisible("epochs").unwrap();
+    assert_eq!(
+        param_box
+            .iter()
+            .filter(|(_, p)| p.value_string.is_some())
+            .count(),
+        1
+    );
+    let views: Vec<(&str, ParameterView)> = param_box.iter().collect();
+    assert_eq!(views[0].0, "lr");
+    assert_eq!(views[0].1.type_name, "f64");
+    assert_eq!(views[0].1.value_string, Some("0.5"));
+    assert_eq!(views[0].1.range_string, (Some("0 <"), Some("< 1")));
+    assert_eq!(views[1].0, "epochs");
+    assert!(!views[1].1.visible);
+}
+
+#[test]
+fn iter_set_values_works() {
+    let mut param_box = ParameterBox::new();
+    param_box.add::<f64>("lr").unwrap();
+    param_box.add::<u32>("epochs").unwrap();
+    param_box.add::<String>("label").unwrap();
+    param_box.set_value::<u32>("epochs", 10).unwrap();
+    param_box.set_value("lr", 0.5).unwrap();
+    assert_eq!(
+        param_box.iter_set_values().collect::<Vec<_>>(),
+        vec![("lr", "0.5"), ("epochs", "10")]
+    );
+}
+
+#[test]
+fn to_string_map_works() {
+    let mut param_box = ParameterBox::new();
+    param_box.add::<f64>("lr").unwrap();
+    param_box.add::<u32>("epochs").unwrap();
+    param_box.set_value("lr", 0.5).unwrap();
+    let map = param_box.to_string_map();
+    assert_eq!(map.len(), 1);
+    assert_eq!(map["lr"], "0.5");
+}
+
+#[test]
+fn merge_from_works() {
+    let mut base_box = ParameterBox::new();
+    base_box.add_with_value("lr", 0.5).unwrap();
+    base_box.add_with_value::<u32>("epochs", 10).unwrap();
+    base_box.set_range_close_close("lr", (0.0, 1.0)).unwrap();
+    let mut override_box = ParameterBox::new();
+    override_box.add_with_value("lr", 0.25).unwrap();
+    override_box
+        .add_with_value("other", "x".to_string())
+        .unwrap();
+    override_box.add::<u32>("epochs").unwrap();
+    base_box.merge_from(&override_box).unwrap();
+    assert_eq!(base_box.clone_value::<f64>("lr").unwrap().unwrap(), 0.25);
+    assert_eq!(base_box.clone_value::<u32>("epochs").unwrap().unwrap(), 10);
+
+    override_box.set_value("lr", 2.0).unwrap();
+    override_box.remove("epochs").unwrap();
+    override_box
+        .add_with_value("epochs", "many".to_string())
+        .unwrap();
+    match base_box.merge_from(&override_box) {
+        Err(ParameterBoxError::InvalidCondition(err_msg)) => {
+            assert_eq!(err_msg.lines().count(), 2);
+        }
+        _ => panic!("errors must be combined"),
+    }
+}
+
+#[test]
+fn read_file_lenient_works() {
+    let filename = std::env::temp_dir().join("parameter_box_test_read_file_lenient.txt");
+    let filename = filename.to_str().unwrap();
+    let mut param_box = ParameterBox::new();
+    param_box.add::<u32>("epochs").unwrap();
+    param_box
+        .set_range_close_close::<u32>("epochs", (1, 100))
+        .unwrap();
+
+    std::fs::write(filename, "epochs 10\nother_program_param 3\n").unwrap();
+    assert!(param_box.read_file(filename).is_err());
+    param_box.read_file_lenient(filename).unwrap();
+    assert_eq!(param_box.clone_value::<u32>("epochs").unwrap().unwrap(), 10);
+
+    std::fs::write(filename, "epochs 10\nepochs 20\n").unwrap();
+    assert!(param_box.read_file_lenient(filename).is_err());
+    std::fs::write(filename, "epochs 1000\n").unwrap();
+    assert!(param_box.read_file_lenient(filename).is_err());
+    std::fs::remove_file(filename).unwrap();
+}
+
+#[test]
+fn read_file_if_exists_works() {
+    let filename = std::env::temp_dir().join("parameter_box_test_read_file_if_exists.txt");
+    let filename = filename.to_str().unwrap();
+    let mut param_box = ParameterBox::new();
+    param_box.add::<u32>("epochs").unwrap();
+    let _ = std::fs::remove_file(filename);
+    param_box.read_file_if_exists(filename).unwrap();
+    assert!(!param_box.is_set("epochs").unwrap());
+
+    std::fs::write(filename, "epochs 10\n").unwrap();
+    param_box.read_file_if_exists(filename).unwrap();
+    assert_eq!(param_box.clone_value::<u32>("epochs").unwrap().unwrap(), 10);
+    std::fs::write(filename, "epochs ten\n").unwrap();
+    assert!(param_box.read_file_if_exists(filename).is_err());
+    std::fs::remove_file(filename).unwrap();
+
+    let dirname = std::env::temp_dir();
+    assert!(matches!(
+        param_box.read_file_if_exists(dirname.to_str().unwrap()),
+        Err(ParameterBoxError::IoError(..))
+    ));
+}
+
+#[test]
+fn read_files_works() {
+    let dir = std::env::temp_dir();
+    let system = dir.join("parameter_box_test_read_files_system.txt");
+    let user = dir.join("parameter_box_test_read_files_user.txt");
+    let (system, user) = (system.to_str().unwrap(), user.to_str().unwrap());
+    let mut param_box = ParameterBox::new();
+    param_box.add::<u32>("epochs").unwrap();
+    param_box.add::<f64>("lr").unwrap();
+    param_box.mark_required("lr").unwrap();
+
+    std::fs::write(system, "epochs 10\nlr 0.5\n").unwrap();
+    std::fs::write(user, "lr 0.25\n").unwrap();
+    param_box.read_files(&[system, user]).unwrap();
+    assert_eq!(param_box.clone_value::<u32>("epochs").unwrap().unwrap(), 10);
+    assert_eq!(param_box.clone_value::<f64>("lr").unwrap().unwrap(), 0.25);
+
+    std::fs::write(system, "epochs ten\n").unwrap();
+    std::fs::write(user, "lr 0.1\nlr 0.2\n").unwrap();
+    match param_box.read_files(&[system, user]) {
+        Err(ParameterBoxError::InvalidInputFile(err_msg)) => {
+            assert_eq!(err_msg.lines().count(), 2);
+            assert!(err_msg.contains(system));
+            assert!(err_msg.contains(user));
+        }
+        _ => panic!("errors in both files must be reported"),
+    }
+    std::fs::remove_file(system).unwrap();
+    std::fs::remove_file(user).unwrap();
+}
+
+#[test]
+fn read_file_reads_usize_and_isize() {
+    let filename = std::env::temp_dir().join("parameter_box_test_read_usize_isize.txt");
+    let filename = filename.to_str().unwrap();
+    let mut param_box = ParameterBox::new();
+    param_box.add::<usize>("n").unwrap();
+    param_box.add::<isize>("offset").unwrap();
+    std::fs::write(filename, "n 42\noffset -7\n").unwrap();
+    param_box.read_file(filename).unwrap();
+    std::fs::remove_file(filename).unwrap();
+    assert_eq!(param_box.clone_value::<usize>("n").unwrap().unwrap(), 42);
+    assert_eq!(
+        param_box.clone_value::<isize>("offset").unwrap().unwrap(),
+        -7
+    );
+}
+
+#[test]
+fn read_file_reads_char() {
+    let filename = std::env::temp_dir().join("parameter_box_test_read_char.txt");
+    let filename = filename.to_str().unwrap();
+    let mut param_box = ParameterBox::new();
+    param_box.add::<char>("separator").unwrap();
+    param_box
+        .set_whitelist("separator", vec![',', ';', ':'])
+        .unwrap();
+    std::fs::write(filename, "separator ,\n").unwrap();
+    param_box.read_file(filename).unwrap();
+    assert_eq!(
+        param_box.clone_value::<char>("separator").unwrap().unwrap(),
+        ','
+    );
+    std::fs::write(filename, "separator |\n").unwrap();
+    assert!(param_box.read_file(filename).is_err());
+    std::fs::write(filename, "separator ab\n").unwrap();
+    assert!(param_box.read_file(filename).is_err());
+    std::fs::remove_file(filename).unwrap();
+}
+
+#[test]
+fn print_markdown_works() {
+    let mut param_box = ParameterBox::new();
+    param_box.add_with_value("lr", 0.5).unwrap();
+    param_box.set_range_open_close("lr", (0.0, 1.0)).unwrap();
+    param_box
+        .set_explanation("lr", "learning | rate".to_string())
+        .unwrap();
+    param_box.add::<u32>("seed").unwrap();
+    param_box.set_invisible("seed").unwrap();
+
+    let mut output = Vec::new();
+    param_box.print_markdown(&mut output, false).unwrap();
+    assert_eq!(
+        String::from_utf8(output).unwrap(),
+        "| Name | Type | Default Value | Range/Constraint | Explanation |\n\
+         |---|---|---|---|---|\n\
+         | lr | f64 | 0.5 | Range: 0 < lr ≦ 1 | learning \\| rate |\n"
+    );
+    let mut output = Vec::new();
+    param_box.print_markdown(&mut output, true).unwrap();
+    assert!(String::from_utf8(output)
+        .unwrap()
+        .ends_with("| seed (hidden) | u32 |  |  |  |\n"));
+}
+
+#[test]
+fn print_table_aligns_columns() {
+    let mut param_box = ParameterBox::new();
+    param_box.add_with_value("a", 1_u8).unwrap();
+    param_box
+        .add_with_value("a_long_parameter_name", 20000_u16)
+        .unwrap();
+    param_box
+        .set_explanation("a", "short one".to_string())
+        .unwrap();
+    param_box.add::<bool>("hidden").unwrap();
+    param_box.set_invisible("hidden").unwrap();
+
+    let mut output = Vec::new();
+    param_box.print_table(&mut output).unwrap();
+    assert_eq!(
+        String::from_utf8(output).unwrap(),
+        "Name                  | Type | Value | Constraint | Explanation\n\
+         ----------------------+------+-------+------------+------------\n\
+         a                     | u8   | 1     |            | short one\n\
+         a_long_parameter_name | u16  | 20000 |            |\n"
+    );
+}
+
+#[test]
+fn print_to_string_works() {
+    let mut param_box = ParameterBox::new();
+    param_box.add_with_value("n", 3_i32).unwrap();
+    let mut output = Vec::new();
+    param_box.print(&mut output).unwrap();
+    assert_eq!(
+        param_box.print_to_string().unwrap(),
+        String::from_utf8(output).unwrap()
+    );
+}
+
+#[test]
+fn print_set_only_skips_unset_and_invisible() {
+    let mut param_box = ParameterBox::new();
+    param_box.add_with_value("set", 1_u8).unwrap();
+    param_box.add::<u8>("unset").unwrap();
+    param_box.add_with_value("hidden", 2_u8).unwrap();
+    param_box.set_invisible("hidden").unwrap();
+
+    let mut output = Vec::new();
+    param_box.print_set_only(&mut output).unwrap();
+    let output = String::from_utf8(output).unwrap();
+    assert!(output.starts_with("set\n"));
+    assert!(!output.contains("unset"));
+    assert!(!output.contains("hidden"));
+}
+
+#[test]
+fn print_sorted_orders_by_name() {
+    let mut param_box = ParameterBox::new();
+    param_box.add_with_value("zeta", 1_u8).unwrap();
+    param_box.add_with_value("alpha", 2_u8).unwrap();
+    param_box.add_with_value("mu", 3_u8).unwrap();
+
+    let mut output = Vec::new();
+    param_box.print_sorted(&mut output).unwrap();
+    let output = String::from_utf8(output).unwrap();
+    let alpha = output.find("alpha\n").unwrap();
+    let mu = output.find("mu\n").unwrap();
+    let zeta = output.find("zeta\n").unwrap();
+    assert!(alpha < mu && mu < zeta);
+    assert_eq!(
+        param_box.parameter_names().collect::<Vec<&str>>(),
+        vec!["zeta", "alpha", "mu"]
+    );
+}
+
+#[test]
+fn parameter_info_works() {
+    let mut param_box = ParameterBox::new();
+    param_box.add_with_value("n", 2_i32).unwrap();
+    param_box.set_range_close_open("n", (0, 10)).unwrap();
+    param_box
+        .set_list("n", ListCondition::White(vec![1, 2, 3]))
+        .unwrap();
+    param_box.set_explanation("n", "count".to_string()).unwrap();
+    param_box.set_invisible("n").unwrap();
+
+    assert_eq!(
+        param_box.parameter_info("n").unwrap(),
+        ParameterInfo {
+            name: "n",
+            type_name: "i32",
+            value_string: Some("2"),
+            range_min: Some("0 ≦"),
+            range_max: Some("< 10"),
+            list_display: Some("[1, 2, 3]"),
+            explanation: Some("count"),
+            visible: false,
+            required: false,
+        }
+    );
+    assert!(matches!(
+        param_box.parameter_info("missing"),
+        Err(ParameterBoxError::NotAdded(_))
+    ));
+}
+
+#[test]
+fn with_capacity_creates_empty_box() {
+    let mut param_box = ParameterBox::with_capacity(100);
+    assert!(param_box.is_empty());
+    for i in 0..100 {
+        param_box.add::<u32>(&format!("param_{}", i)).unwrap();
+    }
+    assert_eq!(param_box.len(), 100);
+    assert!(ParameterBox::default().is_empty());
+}
+
+#[test]
+fn sections_work() {
+    let mut param_box = ParameterBox::new();
+    param_box.add_with_value("dt", 0.1).unwrap();
+    param_box.add_with_value("seed", 7_u64).unwrap();
+    param_box.add_with_value("steps", 10_u32).unwrap();
+    param_box
+        .add_with_value("output", "out".to_string())
+        .unwrap();
+    param_box.set_section("dt", "time").unwrap();
+    param_box.set_section("seed", "random").unwrap();
+    param_box.set_section("steps", "time").unwrap();
+    assert!(param_box.set_section("missing", "time").is_err());
+    assert_eq!(param_box.section_names(), vec!["time", "random"]);
+
+    let mut output = Vec::new();
+    param_box.print_section("time", &mut output).unwrap();
+    let output = String::from_utf8(output).unwrap();
+    assert!(output.starts_with("dt\n"));
+    assert!(output.contains("steps\n"));
+    assert!(!output.contains("seed"));
+
+    let mut output = Vec::new();
+    param_box.print_grouped(&mut output).unwrap();
+    let output = String::from_utf8(output).unwrap();
+    assert!(output.starts_with("[time]\n\ndt\n"));
+    let random = output.find("[random]\n\nseed\n").unwrap();
+    let ungrouped = output.find("[Ungrouped]\n\noutput\n").unwrap();
+    assert!(output.find("steps\n").unwrap() < random && random < ungrouped);
+}
+
+#[test]
+fn warning_range_does_not_fail_set_value() {
+    let mut param_box = ParameterBox::new();
+    param_box.add_with_value("dt", 0.5).unwrap();
+    param_box.set_range_open_close("dt", (0.0, 10.0)).unwrap();
+    param_box
+        .set_warning_range_open_close("dt", (0.0, 0.1))
+        .unwrap();
+    assert_eq!(
+        param_box.get_warnings(),
+        ["`dt` = 0.5 does not satisfy the advisory condition that `dt` ≦ 0.1.".to_string()]
+    );
+    param_box.clear_warnings();
+    assert!(param_box.get_warnings().is_empty());
+
+    param_box.set_value("dt", 0.05).unwrap();
+    assert!(param_box.get_warnings().is_empty());
+    param_box.set_value("dt", 2.0).unwrap();
+    assert_eq!(param_box.get_warnings().len(), 1);
+    assert_eq!(param_box.clone_value::<f64>("dt").unwrap(), Some(2.0));
+    assert!(param_box.set_value("dt", 20.0).is_err());
+    assert_eq!(param_box.get_warnings().len(), 1);
+    assert_eq!(param_box.get_num_errors(), 1);
+    assert!(param_box
+        .print_to_string()
+        .unwrap()
+        .contains("Warning range | 0 < dt ≦ 0.1\n"));
+}
+
+#[test]
+fn sealed_parameter_box_is_read_only() {
+    let mut param_box = ParameterBox::new();
+    param_box.add_with_value("n", 4_u32).unwrap();
+    param_box.add::<f64>("x").unwrap();
+    let sealed = param_box.seal();
+
+    assert_eq!(sealed.clone_value::<u32>("n").unwrap(), Some(4));
+    assert_eq!(sealed.ref_value::<f64>("x").unwrap(), &None);
+    assert_eq!(sealed.clone_value_forcibly::<u32>("n"), 4);
+    assert!(matches!(
+        sealed.clone_value::<u32>("missing"),
+        Err(ParameterBoxError::NotAdded(_))
+    ));
+    assert!(sealed.is_set("n").unwrap());
+    assert!(!sealed.is_set("x").unwrap());
+    assert!(sealed.contains("x"));
+    assert_eq!(sealed.len(), 2);
+    assert_eq!(
+        sealed.parameter_names().collect::<Vec<&str>>(),
+        vec!["n", "x"]
+    );
+    let mut output = Vec::new();
+    sealed.print(&mut output).unwrap();
+    assert!(String::from_utf8(output).unwrap().starts_with("n\n"));
+}
+
+#[test]
+fn any_errors_works() {
+    let mut param_box = ParameterBox::new();
+    param_box.add::<u8>("n").unwrap();
+    assert!(!param_box.any_errors());
+    assert!(param_box.set_value("missing", 1_u8).is_err());
+    assert!(param_box.any_errors());
+    assert_eq!(param_box.get_num_errors(), 1);
+}
+
+#[test]
+fn reset_error_counter_works() {
+    let mut param_box = ParameterBox::new();
+    param_box.add::<u8>("n").unwrap();
+    assert!(param_box.set_value("unknown", 1_u8).is_err());
+    let snapshot = param_box.error_counter_snapshot();
+    assert_eq!(snapshot, 1);
+    assert!(param_box.set_value("unknown", 1_u8).is_err());
+    assert_eq!(param_box.error_counter_snapshot() - snapshot, 1);
+    param_box.reset_error_counter();
+    assert_eq!(param_box.get_num_errors(), 0);
+    assert!(!param_box.any_errors());
+}
+
+#[test]
+fn panic_result_works() {
+    let mut param_box = ParameterBox::new();
+    parameter_box::panic_result!(param_box.add::<u32>("n"));
+    parameter_box::panic_result!(param_box.set_value("n", 5_u32));
+    assert_eq!(
+        parameter_box::panic_result!(param_box.clone_value::<u32>("n")),
+        Some(5)
+    );
+    let panic = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        parameter_box::panic_result!(param_box.set_value("missing", 1_u32))
+    }))
+    .unwrap_err();
+    assert!(panic
+        .downcast_ref::<String>()
+        .unwrap()
+        .contains("`missing`"));
+}
+
+#[test]
+fn try_value_works() {
+    let mut param_box = ParameterBox::new();
+    param_box.add_with_value("n", 3_i64).unwrap();
+    param_box.add::<i64>("unset").unwrap();
+    assert_eq!(param_box.try_value::<i64>("n").unwrap(), 3);
+    let err = param_box.try_value::<i64>("unset").unwrap_err();
+    assert!(matches!(&err, ParameterBoxError::NotSet(name) if name == "unset"));
+    assert_eq!(err.to_string(), "`unset` does not have a value.");
+    assert!(matches!(
+        param_box.try_value::<i64>("missing"),
+        Err(ParameterBoxError::NotAdded(_))
+    ));
+}
+
+#[test]
+fn clone_value_or_works() {
+    let mut param_box = ParameterBox::new();
+    param_box
+        .add_with_value("name", "given".to_string())
+        .unwrap();
+    param_box.add::<u16>("port").unwrap();
+    assert_eq!(
+        param_box.clone_value_or("name", "fallback".to_string()),
+        "given"
+    );
+    assert_eq!(param_box.clone_value_or("port", 8080_u16), 8080);
+    assert_eq!(param_box.get_num_errors(), 0);
+}
+
+#[test]
+#[should_panic(expected = "`missing`")]
+fn clone_value_or_panics_if_not_added() {
+    let mut param_box = ParameterBox::new();
+    param_box.clone_value_or("missing", 0_u8);
+}
+
+#[test]
+fn value_getters_take_shared_reference() {
+    let mut param_box = ParameterBox::new();
+    param_box.add_with_value("n", 1_u8).unwrap();
+    let shared = &param_box;
+    assert_eq!(shared.ref_value::<u8>("n").unwrap(), &Some(1));
+    assert_eq!(shared.clone_value::<u8>("n").unwrap(), Some(1));
+    assert!(shared.clone_value::<u8>("missing").is_err());
+    assert_eq!(param_box.get_num_errors(), 0);
+}
+
+#[test]
+fn add_flag_works() {
+    let mut param_box = ParameterBox::new();
+    param_box.add_flag("verbose").unwrap();
+    param_box.add_flag_on("use_cache").unwrap();
+    assert_eq!(
+        param_box.clone_value::<bool>("verbose").unwrap(),
+        Some(false)
+    );
+    assert_eq!(
+        param_box.clone_value::<bool>("use_cache").unwrap(),
+        Some(true)
+    );
+    assert!(param_box.print_to_string().unwrap().starts_with(
+        "verbose\n----------------------------\nType          | bool\nDefault value | false\n"
+    ));
+
+    for (value_string, expected) in [
+        ("TRUE", true),
+        ("no", false),
+        ("Yes", true),
+        ("0", false),
+        ("1", true),
+        ("False", false),
+    ] {
+        param_box
+            .read_from_str(&format!("verbose {}", value_string))
+            .unwrap();
+        assert_eq!(
+            param_box.clone_value::<bool>("verbose").unwrap(),
+            Some(expected)
+        );
+    }
+    assert!(param_box.read_from_str("verbose maybe").is_err());
+}
+
+#[test]
+fn add_many_reports_all_conflicts() {
+    let mut param_box = ParameterBox::new();
+    param_box.add::<f64>("b").unwrap();
+    param_box.add::<f64>("d").unwrap();
+    match param_box.add_many::<f64>(&["a", "b", "c", "d", "e"]) {
+        Err(ParameterBoxError::AlreadyAdded(err_msg)) => {
+            assert_eq!(err_msg.lines().count(), 2);
+            assert!(err_msg.contains("`b`") && err_msg.contains("`d`"));
+        }
+        _ => panic!("add_many must fail"),
+    }
+    assert_eq!(
+        param_box.parameter_names().collect::<Vec<&str>>(),
+        vec!["b", "d", "a", "c", "e"]
+    );
+    assert_eq!(param_box.get_num_errors(), 2);
+    param_box.add_many::<u8>(&["x", "y"]).unwrap();
+}
+
+#[test]
+fn read_quoted_values() {
+    let mut param_box = ParameterBox::new();
+    param_box.add::<String>("output_dir").unwrap();
+    param_box.add::<String>("title").unwrap();
+    param_box.add::<String>("plain").unwrap();
+    param_box
+        .read_from_str(
+            "output_dir \"/my path/with spaces\"\n\
+             title 'say \"hi\"'\n\
+             plain \"a \\\"quoted\\\" word\"\n",
+        )
+        .unwrap();
+    assert_eq!(
+        param_box.clone_value::<String>("output_dir").unwrap(),
+        Some("/my path/with spaces".to_string())
+    );
+    assert_eq!(
+        param_box.clone_value::<String>("title").unwrap(),
+        Some("say \"hi\"".to_string())
+    );
+    assert_eq!(
+        param_box.clone_value::<String>("plain").unwrap(),
+        Some("a \"quoted\" word".to_string())
+    );
+    assert!(param_box.read_from_str("title two words").is_err());
+    assert!(param_box.read_from_str("title \"unterminated").is_err());
+
+    let filename = std::env::temp_dir().join("parameter_box_quoted_values.txt");
+    let filename = filename.to_str().unwrap();
+    param_box.write_file(filename).unwrap();
+    let mut read_box = ParameterBox::new();
+    read_box
+        .add_many::<String>(&["output_dir", "title", "plain"])
+        .unwrap();
+    read_box.read_file(filename).unwrap();
+    std::fs::remove_file(filename).unwrap();
+    assert_eq!(read_box.to_string_map(), param_box.to_string_map());
+}
+
+#[test]
+fn read_file_with_options_accepts_separators() {
+    let filename = std::env::temp_dir().join("parameter_box_separators.txt");
+    let filename = filename.to_str().unwrap();
+    let read = |separator: Separator, content: &str| {
+        std::fs::write(filename, content).unwrap();
+        let mut param_box = ParameterBox::new();
+        param_box.add::<f64>("lr").unwrap();
+        param_box.add::<String>("path").unwrap();
+        let result = param_box
+            .read_file_with_options(filename, &ParameterFileOptions::new().separator(separator));
+        std::fs::remove_file(filename).unwrap();
+        result.map(|()| {
+            (
+                param_box.clone_value::<f64>("lr").unwrap(),
+                param_box.clone_value::<String>("path").unwrap(),
+            )
+        })
+    };
+    let expected = (Some(0.001), Some("C:/data".to_string()));
+    assert_eq!(
+        read(Separator::Whitespace, "lr 0.001\npath C:/data\n").unwrap(),
+        expected
+    );
+    assert_eq!(
+        read(Separator::Equals, "lr=0.001\npath = C:/data\n").unwrap(),
+        expected
+    );
+    assert_eq!(
+        read(Separator::Colon, "lr: 0.001\npath:C:/data\n").unwrap(),
+        expected
+    );
+    assert_eq!(
+        read(Separator::Any, "lr=0.001\npath: C:/data\n").unwrap(),
+        expected
+    );
+    assert!(read(Separator::Equals, "lr 0.001\n").is_err());
+    assert!(read(Separator::Whitespace, "lr=0.001\n").is_err());
+}
+
+#[test]
+fn read_strips_inline_comments() {
+    let mut param_box = ParameterBox::new();
+    param_box.add::<f64>("lr").unwrap();
+    param_box.add::<String>("label").unwrap();
+    param_box.add::<String>("color").unwrap();
+    param_box
+        .read_from_str(
+            "lr 0.001 # learning rate\n\
+             label \"value with # hash\"   # comment\n\
+             color '#ff0000'#red\n",
+        )
+        .unwrap();
+    assert_eq!(param_box.clone_value::<f64>("lr").unwrap(), Some(0.001));
+    assert_eq!(
+        param_box.clone_value::<String>("label").unwrap(),
+        Some("value with # hash".to_string())
+    );
+    assert_eq!(
+        param_box.clone_value::<String>("color").unwrap(),
+        Some("#ff0000".to_string())
+    );
+    assert!(param_box.read_from_str("lr # no value").is_err());
+}
+
+#[test]
+fn type_name_of_works() {
+    let mut param_box = ParameterBox::new();
+    param_box.add::<f64>("x").unwrap();
+    param_box.add::<String>("s").unwrap();
+    assert_eq!(param_box.type_name_of("x").unwrap(), "f64");
+    assert_eq!(
+        param_box.type_name_of("s").unwrap(),
+        std::any::type_name::<String>()
+    );
+    assert!(matches!(
+        param_box.type_name_of("missing"),
+        Err(ParameterBoxError::NotAdded(_))
+    ));
+}
+
+#[test]
+fn is_type_works() {
+    let mut param_box = ParameterBox::new();
+    param_box.add::<i32>("n").unwrap();
+    assert!(param_box.is_type::<i32>("n").unwrap());
+    assert!(!param_box.is_type::<f64>("n").unwrap());
+    assert!(matches!(
+        param_box.is_type::<i32>("missing"),
+        Err(ParameterBoxError::NotAdded(_))
+    ));
+}
+
+#[test]
+fn wrong_type_access_returns_type_mismatch() {
+    let mut param_box = ParameterBox::new();
+    param_box.add_with_value("n", 1_i32).unwrap();
+    match param_box.clone_value::<f64>("n") {
+        Err(ParameterBoxError::TypeMismatch {
+            name,
+            requested,
+            actual,
+        }) => {
+            assert_eq!(name, "n");
+            assert_eq!(requested, "f64");
+            assert_eq!(actual, "i32");
+        }
+        _ => panic!("clone_value must fail with TypeMismatch"),
+    }
+    assert!(matches!(
+        param_box.set_value("n", 1.5),
+        Err(ParameterBoxError::TypeMismatch { .. })
+    ));
+    assert!(matches!(
+        param_box.set_range_close_close("n", (0_u8, 1_u8)),
+        Err(ParameterBoxError::TypeMismatch { .. })
+    ));
+    assert_eq!(param_box.get_num_errors(), 2);
+    assert_eq!(param_box.clone_value::<i32>("n").unwrap(), Some(1));
+    assert_eq!(
+        param_box.try_value::<u8>("n").unwrap_err().to_string(),
+        "`n` is accessed as u8, but its type is i32."
+    );
+}
+
+#[test]
+fn io_error_keeps_original_error() {
+    let mut param_box = ParameterBox::new();
+    let err = param_box
+        .read_file("/nonexistent/parameter_box_test.txt")
+        .unwrap_err();
+    assert_eq!(err.io_error_kind(), Some(std::io::ErrorKind::NotFound));
+    let cloned = err.clone();
+    assert_eq!(cloned.to_string(), err.to_string());
+    assert_eq!(
+        cloned.io_error().unwrap().kind(),
+        std::io::ErrorKind::NotFound
+    );
+    assert!(param_box
+        .set_invisible("none")
+        .unwrap_err()
+        .io_error_kind()
+        .is_none());
+}
+
+#[test]
+fn set_value_auto_from_string_works() {
+    let mut param_box = ParameterBox::new();
+    param_box.add::<u16>("port").unwrap();
+    param_box.add::<bool>("debug").unwrap();
+    param_box.add::<String>("host").unwrap();
+    param_box
+        .set_value_auto_from_string("port", "8080")
+        .unwrap();
+    param_box
+        .set_value_auto_from_string("debug", "yes")
+        .unwrap();
+    param_box
+        .set_value_auto_from_string("host", "localhost")
+        .unwrap();
+    assert_eq!(param_box.clone_value::<u16>("port").unwrap(), Some(8080));
+    assert_eq!(param_box.clone_value::<bool>("debug").unwrap(), Some(true));
+    assert_eq!(
+        param_box.clone_value::<String>("host").unwrap(),
+        Some("localhost".to_string())
+    );
+    assert!(matches!(
+        param_box.set_value_auto_from_string("port", "http"),
+        Err(ParameterBoxError::InvalidParse(_))
+    ));
+    assert!(matches!(
+        param_box.set_value_auto_from_string("missing", "1"),
+        Err(ParameterBoxError::NotAdded(_))
+    ));
+}
+
+#[test]
+fn set_value_if_unset_works() {
+    let mut param_box = ParameterBox::new();
+    param_box.add_with_value("lr", 0.1).unwrap();
+    param_box.add::<f64>("momentum").unwrap();
+    param_box.set_value_if_unset("lr", 0.5).unwrap();
+    param_box.set_value_if_unset("momentum", 0.9).unwrap();
+    assert_eq!(param_box.clone_value::<f64>("lr").unwrap(), Some(0.1));
+    assert_eq!(param_box.clone_value::<f64>("momentum").unwrap(), Some(0.9));
+    assert!(matches!(
+        param_box.set_value_if_unset("missing", 1.0),
+        Err(ParameterBoxError::NotAdded(_))
+    ));
+}
+
+#[test]
+fn count_set_and_unset_work() {
+    let mut param_box = ParameterBox::new();
+    assert_eq!(param_box.total_count(), 0);
+    param_box.add_with_value("a", 1_u8).unwrap();
+    param_box.add::<u8>("b").unwrap();
+    param_box.add::<u8>("c").unwrap();
+    assert_eq!(param_box.count_set(), 1);
+    assert_eq!(param_box.count_unset(), 2);
+    param_box.set_value("b", 2_u8).unwrap();
+    assert_eq!(param_box.count_set(), 2);
+    assert_eq!(param_box.count_unset(), 1);
+    assert_eq!(param_box.total_count(), param_box.len());
+}
+
+#[test]
+fn filter_names_works() {
+    let mut param_box = ParameterBox::new();
+    param_box.add::<f64>("lr").unwrap();
+    param_box.set_range_open_open("lr", (0.0, 1.0)).unwrap();
+    param_box.add::<f64>("momentum").unwrap();
+    param_box.add_with_value("epochs", 10_u32).unwrap();
+    param_box.add::<String>("output").unwrap();
+    param_box.mark_required("output").unwrap();
+
+    assert_eq!(
+        param_box.filter_names(|_, info| info.type_name == "f64" && info.range_min.is_none()),
+        vec!["momentum"]
+    );
+    assert_eq!(
+        param_box.filter_names(|_, info| info.required && info.value_string.is_none()),
+        vec!["output"]
+    );
+    assert_eq!(
+        param_box.filter_names(|name, _| name.len() > 2),
+        vec!["momentum", "epochs", "output"]
+    );
+}
@@ -0,0 +1,44 @@
+#![cfg(feature = "regex")]
+
+use parameter_box::{ParameterBox, ParameterBoxError};
+
+#[test]
+fn set_regex_works() {
+    let mut param_box = ParameterBox::new();
+    param_box.add::<String>("host").unwrap();
+    param_box.add::<u32>("port").unwrap();
+    param_box.set_regex("host", r"^[a-z]+(\.[a-z]+)*$").unwrap();
+    param_box
+        .set_value("host", "example.com".to_string())
+        .unwrap();
+    match param_box.set_value("host", "Example.com".to_string()) {
+        Err(ParameterBoxError::InvalidCondition(err_msg)) => {
+            assert!(err_msg.contains(r"^[a-z]+(\.[a-z]+)*$"));
+        }
+        _ => panic!("the value must not match the regex"),
+    }
+    assert!(param_box.read_from_str("host a_b\n").is_err());
+    assert!(param_box.set_regex("host", "(").is_err());
+    assert!(param_box.set_regex("port", "[0-9]+").is_err());
+
+    param_box
+        .set_value("host", "localhost".to_string())
+        .unwrap();
+    let mut output = Vec::new();
+    param_box.print(&mut output).unwrap();
+    assert!(String::from_utf8(output)
+        .unwrap()
+        .contains(r"Regex         | ^[a-z]+(\.[a-z]+)*$"));
+    assert!(param_box.set_regex("host", "^[0-9]+$").is_err());
+}
+
+#[test]
+fn copy_constraints_from_copies_regex() {
+    let mut param_box = ParameterBox::new();
+    param_box.add::<String>("host").unwrap();
+    param_box.add::<String>("mirror").unwrap();
+    param_box.set_regex("host", "^[a-z]+$").unwrap();
+    param_box.copy_constraints_from("host", "mirror").unwrap();
+    assert!(param_box.set_value("mirror", "a1".to_string()).is_err());
+    param_box.set_value("mirror", "abc".to_string()).unwrap();
+}
@@ -0,0 +1,108 @@
+#![cfg(feature = "serde")]
+
+use parameter_box::ParameterBox;
+
+#[test]
+fn serialize_matches_print_json() {
+    let mut param_box = ParameterBox::new();
+    param_box.add_with_value("lr", 0.5).unwrap();
+    param_box.set_range_open_close("lr", (0.0, 1.0)).unwrap();
+    param_box
+        .set_explanation("lr", "learning rate".to_string())
+        .unwrap();
+    param_box.add::<u32>("epochs").unwrap();
+    param_box.set_blacklist::<u32>("epochs", vec![0]).unwrap();
+    param_box.set_invisible("epochs").unwrap();
+
+    let serialized = serde_json::to_value(&param_box).unwrap();
+    assert_eq!(serialized["lr"]["value"], "0.5");
+    assert_eq!(serialized["epochs"]["value"], serde_json::Value::Null);
+    assert_eq!(serialized["epochs"]["list_type"], "blacklist");
+    assert_eq!(
+        serialized["epochs"]["list_values"],
+        serde_json::json!(["0"])
+    );
+    assert_eq!(serialized["epochs"]["visible"], false);
+
+    let mut output = Vec::new();
+    param_box.print_json(&mut output).unwrap();
+    let printed: serde_json::Value = serde_json::from_slice(&output).unwrap();
+    assert_eq!(serialized, printed);
+}
+
+#[cfg(feature = "serde_json")]
+#[test]
+fn populate_from_value_works() {
+    let mut param_box = ParameterBox::new();
+    param_box.add::<f64>("lr").unwrap();
+    param_box.add::<u32>("epochs").unwrap();
+    param_box.add::<bool>("verbose").unwrap();
+    param_box.add::<String>("label").unwrap();
+    param_box.set_range_close_close("lr", (0.0, 1.0)).unwrap();
+    param_box
+        .populate_from_value(&serde_json::json!({
+            "lr": "0.5",
+            "epochs": 10,
+            "verbose": true,
+            "label": null,
+        }))
+        .unwrap();
+    assert_eq!(param_box.clone_value::<f64>("lr").unwrap().unwrap(), 0.5);
+    assert_eq!(param_box.clone_value::<u32>("epochs").unwrap().unwrap(), 10);
+    assert!(param_box.clone_value::<bool>("verbose").unwrap().unwrap());
+    assert!(!param_box.is_set("label").unwrap());
+
+    let mut copied_box = ParameterBox::new();
+    copied_box.add::<f64>("lr").unwrap();
+    copied_box.add::<u32>("epochs").unwrap();
+    copied_box.add::<bool>("verbose").unwrap();
+    copied_box.add::<String>("label").unwrap();
+    copied_box
+        .populate_from_value(&serde_json::to_value(&param_box).unwrap())
+        .unwrap();
+    assert_eq!(
+        copied_box.get_all_values_as_strings(),
+        param_box.get_all_values_as_strings()
+    );
+
+    let result = param_box.populate_from_value(&serde_json::json!({
+        "lr": 2.0,
+        "epochs": "ten",
+        "unknown": 1,
+        "label": [1],
+    }));
+    match result {
+        Err(parameter_box::ParameterBoxError::InvalidInputFile(err_msg)) => {
+            assert_eq!(err_msg.lines().count(), 4);
+        }
+        _ => panic!("errors must be combined"),
+    }
+    assert!(param_box
+        .populate_from_value(&serde_json::json!([1, 2]))
+        .is_err());
+}
+
+#[cfg(feature = "serde_json")]
+#[test]
+fn load_explanations_from_json_file_works() {
+    let filename = std::env::temp_dir().join("parameter_box_test_explanations.json");
+    std::fs::write(
+        &filename,
+        "{\"lr\": \"Learning rate\", \"epochs\": \"Number of \\\"epochs\\\"\"}",
+    )
+    .unwrap();
+
+    let mut param_box = ParameterBox::new();
+    param_box.add::<f64>("lr").unwrap();
+    param_box.add::<u32>("epochs").unwrap();
+    param_box
+        .load_explanations_from_file(filename.to_str().unwrap())
+        .unwrap();
+    std::fs::remove_file(&filename).unwrap();
+
+    assert_eq!(param_box.get_explanation("lr"), Some("Learning rate"));
+    assert_eq!(
+        param_box.get_explanation("epochs"),
+        Some("Number of \"epochs\"")
+    );
+}
@@ -0,0 +1,256 @@
+#![cfg(feature = "toml")]
+
+use parameter_box::{ParameterBox, ParameterBoxError};
+
+#[test]
+fn from_toml_file_works() {
+    let filename = std::env::temp_dir().join("parameter_box_test_from_toml.toml");
+    std::fs::write(
+        &filename,
+        "n = 3\nlr = 0.5\nverbose = true\nlabel = \"run 1\"\n",
+    )
+    .unwrap();
+    let param_box = ParameterBox::from_toml_file(filename.to_str().unwrap()).unwrap();
+    std::fs::remove_file(&filename).unwrap();
+
+    assert_eq!(param_box.clone_value::<i64>("n").unwrap().unwrap(), 3);
+    assert_eq!(param_box.clone_value::<f64>("lr").unwrap().unwrap(), 0.5);
+    assert!(param_box.clone_value::<bool>("verbose").unwrap().unwrap());
+    assert_eq!(
+        param_box.clone_value::<String>("label").unwrap().unwrap(),
+        "run 1"
+    );
+    assert_eq!(
+        param_box.iter_names_and_types().collect::<Vec<_>>(),
+        vec![
+            ("n", "i64"),
+            ("lr", "f64"),
+            ("verbose", "bool"),
+            ("label", "alloc::string::String"),
+        ]
+    );
+}
+
+#[test]
+fn from_toml_file_rejects_nested_values() {
+    let filename = std::env::temp_dir().join("parameter_box_test_from_toml_nested.toml");
+    std::fs::write(&filename, "n = 3\nlist = [1, 2]\n[table]\nx = 1\n").unwrap();
+    let result = ParameterBox::from_toml_file(filename.to_str().unwrap());
+    std::fs::remove_file(&filename).unwrap();
+
+    assert!(result.is_err());
+}
+
+#[test]
+fn from_toml_file_reads_parameters_table() {
+    let mut param_box = ParameterBox::new();
+    param_box.add::<i64>("n").unwrap();
+    param_box.add::<String>("label").unwrap();
+    param_box.set_value::<i64>("n", 3).unwrap();
+    param_box
+        .set_value::<String>("label", "run".to_string())
+        .unwrap();
+    let filename = std::env::temp_dir().join("parameter_box_test_from_toml_parameters.toml");
+    param_box
+        .write_toml_file(filename.to_str().unwrap())
+        .unwrap();
+    let read_box = ParameterBox::from_toml_file(filename.to_str().unwrap()).unwrap();
+    assert_eq!(
+        read_box.get_all_values_as_strings(),
+        param_box.get_all_values_as_strings()
+    );
+
+    std::fs::write(&filename, "n = 1\n[parameters]\nm = 2\n").unwrap();
+    let result = ParameterBox::from_toml_file(filename.to_str().unwrap());
+    std::fs::remove_file(&filename).unwrap();
+    assert!(result.is_err());
+}
+
+#[test]
+fn read_toml_file_and_write_toml_file_round_trip() {
+    let mut param_box = ParameterBox::new();
+    param_box.add::<u32>("n").unwrap();
+    param_box.add::<f64>("lr").unwrap();
+    param_box.add::<bool>("verbose").unwrap();
+    param_box.add::<String>("label").unwrap();
+    param_box.add::<i32>("unset").unwrap();
+    param_box.set_range_close_close("n", (1_u32, 10)).unwrap();
+
+    let filename = std::env::temp_dir().join("parameter_box_test_read_toml.toml");
+    std::fs::write(
+        &filename,
+        "[parameters]\nn = 3\nlr = 1\nverbose = true\nlabel = \"run \\\"1\\\"\"\n",
+    )
+    .unwrap();
+    param_box
+        .read_toml_file(filename.to_str().unwrap())
+        .unwrap();
+    assert_eq!(param_box.clone_value::<u32>("n").unwrap().unwrap(), 3);
+    assert_eq!(param_box.clone_value::<f64>("lr").unwrap().unwrap(), 1.0);
+    assert_eq!(
+        param_box.clone_value::<String>("label").unwrap().unwrap(),
+        "run \"1\""
+    );
+
+    param_box
+        .write_toml_file(filename.to_str().unwrap())
+        .unwrap();
+    let mut read_box = ParameterBox::new();
+    read_box.add::<u32>("n").unwrap();
+    read_box.add::<f64>("lr").unwrap();
+    read_box.add::<bool>("verbose").unwrap();
+    read_box.add::<String>("label").unwrap();
+    read_box.add::<i32>("unset").unwrap();
+    read_box.read_toml_file(filename.to_str().unwrap()).unwrap();
+    std::fs::remove_file(&filename).unwrap();
+    assert_eq!(
+        read_box.get_all_values_as_strings(),
+        param_box.get_all_values_as_strings()
+    );
+}
+
+#[test]
+fn read_toml_file_reports_errors() {
+    let mut param_box = ParameterBox::new();
+    param_box.add::<u32>("n").unwrap();
+    param_box.add::<String>("label").unwrap();
+    param_box.set_range_close_close("n", (1_u32, 10)).unwrap();
+
+    let filename = std::env::temp_dir().join("parameter_box_test_read_toml_errors.toml");
+    std::fs::write(&filename, "[parameters]\nn = 30\nlabel = 1\nunknown = 1\n").unwrap();
+    let result = param_box.read_toml_file(filename.to_str().unwrap());
+    std::fs::remove_file(&filename).unwrap();
+    match result {
+        Err(ParameterBoxError::InvalidInputFile(err_msg)) => {
+            assert_eq!(err_msg.lines().count(), 3);
+        }
+        _ => panic!("errors must be reported"),
+    }
+}
+
+#[test]
+fn read_toml_file_reports_missing_required_parameters() {
+    let mut param_box = ParameterBox::new();
+    param_box.add::<u32>("n").unwrap();
+    param_box.add::<u32>("m").unwrap();
+    param_box.mark_required("m").unwrap();
+
+    let filename = std::env::temp_dir().join("parameter_box_test_read_toml_required.toml");
+    std::fs::write(&filename, "[parameters]\nn = 3\n").unwrap();
+    let result = param_box.read_toml_file(filename.to_str().unwrap());
+    std::fs::remove_file(&filename).unwrap();
+    match result {
+        Err(ParameterBoxError::InvalidInputFile(err_msg)) => {
+            assert!(err_msg.contains("`m`"));
+        }
+        _ => panic!("a missing required parameter must be reported"),
+    }
+    assert_eq!(param_box.clone_value::<u32>("n").unwrap().unwrap(), 3);
+}
+
+#[test]
+fn load_explanations_from_file_works() {
+    let filename = std::env::temp_dir().join("parameter_box_test_explanations.toml");
+    std::fs::write(
+        &filename,
+        "# Explanations\nlr = \"Learning rate = step size\"\nlabel = 'Run \"label\"'\n\nepochs=\"Number of \\\"epochs\\\"\"\n",
+    )
+    .unwrap();
+
+    let mut param_box = ParameterBox::new();
+    param_box.add::<f64>("lr").unwrap();
+    param_box.add::<String>("label").unwrap();
+    param_box.add::<u32>("epochs").unwrap();
+    param_box
+        .load_explanations_from_file(filename.to_str().unwrap())
+        .unwrap();
+    std::fs::remove_file(&filename).unwrap();
+
+    assert_eq!(
+        param_box.ref_explanation("lr").unwrap().as_deref(),
+        Some("Learning rate = step size")
+    );
+    assert_eq!(
+        param_box.ref_explanation("label").unwrap().as_deref(),
+        Some("Run \"label\"")
+    );
+    assert_eq!(
+        param_box.ref_explanation("epochs").unwrap().as_deref(),
+        Some("Number of \"epochs\"")
+    );
+}
+
+#[test]
+fn load_explanations_from_file_reports_bad_entries() {
+    let filename = std::env::temp_dir().join("parameter_box_test_bad_explanations.toml");
+    std::fs::write(&filename, "lr = 1\nunknown = \"x\"\n").unwrap();
+
+    let mut param_box = ParameterBox::new();
+    param_box.add::<f64>("lr").unwrap();
+    let result = param_box.load_explanations_from_file(filename.to_str().unwrap());
+    std::fs::remove_file(&filename).unwrap();
+
+    assert!(result.is_err());
+    assert_eq!(param_box.get_num_errors(), 2);
+}
+
+#[test]
+fn write_toml_file_round_trips_large_integers() {
+    let mut param_box = ParameterBox::new();
+    param_box.add::<u64>("max").unwrap();
+    param_box.add::<i128>("min").unwrap();
+    param_box.add::<u64>("small").unwrap();
+    param_box.set_value("max", u64::MAX).unwrap();
+    param_box.set_value("min", i128::MIN).unwrap();
+    param_box.set_value("small", 7_u64).unwrap();
+
+    let filename = std::env::temp_dir().join("parameter_box_test_toml_large_integers.toml");
+    param_box
+        .write_toml_file(filename.to_str().unwrap())
+        .unwrap();
+    let content = std::fs::read_to_string(&filename).unwrap();
+    assert!(content.contains(&format!("max = \"{}\"", u64::MAX)));
+    assert!(content.contains("small = 7\n"));
+
+    let mut read_box = ParameterBox::new();
+    read_box.add::<u64>("max").unwrap();
+    read_box.add::<i128>("min").unwrap();
+    read_box.add::<u64>("small").unwrap();
+    read_box.read_toml_file(filename.to_str().unwrap()).unwrap();
+    std::fs::remove_file(&filename).unwrap();
+    assert_eq!(
+        read_box.clone_value::<u64>("max").unwrap().unwrap(),
+        u64::MAX
+    );
+    assert_eq!(
+        read_box.clone_value::<i128>("min").unwrap().unwrap(),
+        i128::MIN
+    );
+    assert_eq!(read_box.clone_value::<u64>("small").unwrap().unwrap(), 7);
+}
+
+#[test]
+fn write_toml_file_round_trips_non_bare_names() {
+    let mut param_box = ParameterBox::new();
+    param_box.add::<u32>("").unwrap();
+    param_box.add::<u32>("physics.dt").unwrap();
+    param_box.add::<u32>("with space").unwrap();
+    param_box.set_value("", 1_u32).unwrap();
+    param_box.set_value("physics.dt", 2_u32).unwrap();
+    param_box.set_value("with space", 3_u32).unwrap();
+
+    let filename = std::env::temp_dir().join("parameter_box_test_toml_non_bare_names.toml");
+    param_box
+        .write_toml_file(filename.to_str().unwrap())
+        .unwrap();
+    let mut read_box = ParameterBox::new();
+    read_box.add::<u32>("").unwrap();
+    read_box.add::<u32>("physics.dt").unwrap();
+    read_box.add::<u32>("with space").unwrap();
+    read_box.read_toml_file(filename.to_str().unwrap()).unwrap();
+    std::fs::remove_file(&filename).unwrap();
+    assert_eq!(
+        read_box.get_all_values_as_strings(),
+        param_box.get_all_values_as_strings()
+    );
+}